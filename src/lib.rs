@@ -15,7 +15,7 @@
 //!
 //! # Memory Usage
 //!
-//! An empty resizable vector is approximately 72 bytes in size, and while
+//! An empty resizable vector is approximately 80 bytes in size, and while
 //! holding elements it will have a space overhead on the order of O(√N) as
 //! described in the paper. As elements are added the vector will grow by
 //! allocating additional data blocks. Likewise, as elements are removed from
@@ -32,13 +32,75 @@
 //! pointers, and de-allocating memory as needed, there are many `unsafe` blocks
 //! throughout the code.
 
-use std::alloc::{Layout, alloc, dealloc, handle_alloc_error};
-use std::fmt;
-use std::ops::{Index, IndexMut};
+#![cfg_attr(not(any(feature = "std", test)), no_std)]
+
+extern crate alloc;
+
+use alloc::alloc::{Layout, handle_alloc_error};
+use alloc::vec;
+use alloc::vec::Vec;
+use core::fmt;
+use core::hash::{Hash, Hasher};
+use core::ops::{Add, Bound, Index, IndexMut, Mul, RangeBounds};
+use core::ptr::NonNull;
+#[cfg(feature = "std")]
+use std::collections::{HashMap, HashSet};
+
+pub(crate) use self::alloc_api::{Allocator, Global, do_alloc};
+
+// With the `allocator-api2` feature, the blocks may be allocated by any
+// allocator that implements the `Allocator` trait of that crate.
+#[cfg(feature = "allocator-api2")]
+mod alloc_api {
+    use alloc::alloc::Layout;
+    pub use allocator_api2::alloc::{Allocator, Global};
+    use core::ptr::NonNull;
+
+    /// Allocate memory for the given layout, or return `None` on failure.
+    pub(crate) fn do_alloc<A: Allocator>(alloc: &A, layout: Layout) -> Option<NonNull<u8>> {
+        alloc.allocate(layout).ok().map(NonNull::cast)
+    }
+}
+
+// Without the `allocator-api2` feature, only the global allocator is
+// available, and the trait is not visible outside of this crate.
+#[cfg(not(feature = "allocator-api2"))]
+mod alloc_api {
+    use alloc::alloc::{Layout, alloc, dealloc};
+    use core::ptr::NonNull;
+
+    #[allow(clippy::missing_safety_doc)]
+    pub unsafe trait Allocator {
+        fn allocate(&self, layout: Layout) -> Option<NonNull<u8>>;
+        unsafe fn deallocate(&self, ptr: NonNull<u8>, layout: Layout);
+    }
+
+    #[derive(Clone, Copy, Debug, Default)]
+    pub struct Global;
+
+    unsafe impl Allocator for Global {
+        fn allocate(&self, layout: Layout) -> Option<NonNull<u8>> {
+            NonNull::new(unsafe { alloc(layout) })
+        }
+
+        unsafe fn deallocate(&self, ptr: NonNull<u8>, layout: Layout) {
+            unsafe { dealloc(ptr.as_ptr(), layout) }
+        }
+    }
+
+    /// Allocate memory for the given layout, or return `None` on failure.
+    pub(crate) fn do_alloc<A: Allocator>(alloc: &A, layout: Layout) -> Option<NonNull<u8>> {
+        alloc.allocate(layout)
+    }
+}
 
 /// Tiered vector which maintains a collection of circular deques in order to
 /// efficiently support insert and remove from any location within the vector.
-pub struct Vector<T> {
+///
+/// The blocks are allocated by the global allocator unless the
+/// `allocator-api2` feature is enabled, in which case a vector created by
+/// `new_in()` or `with_capacity_in()` uses the given allocator instead.
+pub struct Vector<T, A: Allocator = Global> {
     /// each deque is of size l = 2^k
     k: usize,
     /// bit-mask to get the index into a circular deque
@@ -49,15 +111,198 @@ pub struct Vector<T> {
     upper_limit: usize,
     /// when count decreases to this size, compress the vector
     lower_limit: usize,
+    /// smallest exponent to which the vector will compress
+    min_k: usize,
     /// number of elements in the vector
     count: usize,
     /// dope vector
-    index: Vec<CyclicArray<T>>,
+    index: Vec<CyclicArray<T, A>>,
+    /// block allocation counters
+    #[cfg(feature = "metrics")]
+    stats: AllocStats,
+    /// allocator for the blocks
+    alloc: A,
+}
+
+/// Counts of the block allocations performed by a vector over its lifetime,
+/// including those of any vectors whose blocks it has taken over, and less
+/// those of the blocks it has handed over to another vector.
+#[cfg(feature = "metrics")]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct AllocStats {
+    /// number of blocks allocated
+    pub allocs: usize,
+    /// number of blocks deallocated
+    pub deallocs: usize,
+    /// total number of bytes allocated for blocks
+    pub bytes_allocated: usize,
+}
+
+/// Tier parameters of a vector, as returned by `Vector::tier_info()`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct TierInfo {
+    /// block size exponent
+    pub k: usize,
+    /// number of elements each block can hold
+    pub block_size: usize,
+    /// number of blocks currently allocated
+    pub block_count: usize,
+    /// fewest blocks that can hold the elements at this block size
+    pub ideal_block_count: usize,
+    /// ratio of elements to the capacity of the allocated blocks
+    pub fill_ratio: f64,
 }
 
 impl<T> Vector<T> {
     /// Return an empty vector with zero capacity.
     pub fn new() -> Self {
+        Self::new_in(Global)
+    }
+
+    /// Return an empty vector whose blocks will hold `2^k` elements each,
+    /// rather than starting with a block size of 4. The vector expands from
+    /// that point as usual, but never compresses to blocks smaller than `2^k`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `k` is less than 2, or so large that the vector could not
+    /// hold `2^k` blocks of `2^k` elements.
+    ///
+    /// # Time complexity
+    ///
+    /// Constant time.
+    pub fn with_tier_exponent(k: usize) -> Self {
+        let max = (usize::BITS / 2) as usize;
+        if !(2..max).contains(&k) {
+            panic!("tier exponent (is {k}) should be >= 2 and < {max}");
+        }
+        let mut vector = Self::new();
+        vector.set_exponent(k);
+        vector.min_k = k;
+        vector
+    }
+
+    /// Return an empty vector with blocks already allocated to hold at least
+    /// `n` elements, using the smallest block size that can do so.
+    ///
+    /// # Time complexity
+    ///
+    /// O(√n)
+    pub fn with_capacity(n: usize) -> Self {
+        Self::with_capacity_in(n, Global)
+    }
+
+    /// Return a vector of the given length in which every element is the
+    /// default value of the element type.
+    ///
+    /// # Time complexity
+    ///
+    /// O(n)
+    pub fn with_len(len: usize) -> Self
+    where
+        T: Default,
+    {
+        let mut vector = Self::new();
+        vector.reserve_initialized(len);
+        vector
+    }
+
+    /// Builds a vector by cloning the elements of each of the given slices, in
+    /// order, such that the result is their concatenation.
+    ///
+    /// # Time complexity
+    ///
+    /// O(n) where n is the total length of the slices.
+    pub fn from_slices<'a, I>(slices: I) -> Vector<T>
+    where
+        I: IntoIterator<Item = &'a [T]>,
+        T: Clone + 'a,
+    {
+        let mut vector: Vector<T> = Vector::new();
+        for slice in slices {
+            vector.reserve(slice.len());
+            vector.extend_from_slice(slice);
+        }
+        vector
+    }
+
+    /// Consumes each of the given vectors and appends their elements, in
+    /// order, to a single vector. The blocks of an input are reused as-is when
+    /// its block size matches and the preceding blocks are all full.
+    ///
+    /// # Time complexity
+    ///
+    /// O(n) where n is the total number of elements.
+    pub fn concat_vectors<I: IntoIterator<Item = Vector<T>>>(iter: I) -> Vector<T> {
+        let vectors: Vec<Vector<T>> = iter.into_iter().collect();
+        let mut remaining: usize = vectors.iter().map(|v| v.count).sum();
+        let mut result: Vector<T> = Vector::new();
+        for mut vector in vectors {
+            if vector.count == 0 {
+                continue;
+            }
+            remaining -= vector.count;
+            if result.count == 0 && result.index.is_empty() {
+                result = vector;
+            } else if result.k == vector.k
+                && result.count == result.index.len() << result.k
+                && result.count + vector.count <= result.upper_limit
+            {
+                result.take_stats_from(&mut vector);
+                result.index.append(&mut vector.index);
+                result.count += vector.count;
+                vector.count = 0;
+            } else {
+                result.reserve(vector.count + remaining);
+                for value in vector {
+                    result.push(value);
+                }
+            }
+        }
+        result
+    }
+
+    /// Consumes the given vectors and packs their elements, in order, into a
+    /// single vector. When every input has the same block size, the blocks of
+    /// each input are moved into the result whole, as with `append()`. If the
+    /// result ends in a partial block, only the leading elements of each moved
+    /// block are shifted onto the block before it, without allocating any new
+    /// blocks. The result is reshaped at the end if it holds too many elements
+    /// for that block size. Inputs with differing block sizes are combined by
+    /// moving each element as with `concat_vectors()`.
+    ///
+    /// # Time complexity
+    ///
+    /// O(√N) per input if every input but the last ends on a block boundary,
+    /// otherwise O(n) in the worst case, where n is the total number of
+    /// elements.
+    pub fn pack(vectors: Vec<Vector<T>>) -> Vector<T> {
+        let Some(k) = vectors.first().map(|v| v.k) else {
+            return Vector::new();
+        };
+        if vectors.iter().any(|v| v.k != k) {
+            return Vector::concat_vectors(vectors);
+        }
+        let mut result: Vector<T> = Vector::new();
+        result.set_exponent(k);
+        for mut vector in vectors {
+            result.take_stats_from(&mut vector);
+            let added = core::mem::take(&mut vector.count);
+            result.adopt_blocks(core::mem::take(&mut vector.index));
+            result.count += added;
+        }
+        result.trim_empty_blocks();
+        if result.count > result.upper_limit {
+            result.rebalance();
+        }
+        result
+    }
+}
+
+impl<T, A: Allocator + Clone> Vector<T, A> {
+    /// Return an empty vector with zero capacity, whose blocks will be
+    /// allocated using the given allocator.
+    pub fn new_in(alloc: A) -> Self {
         // default l value of 4 like std::vec::Vec does for its initial
         // allocation (its initial capacity is zero then becomes 4 then doubles
         // with each expansion)
@@ -67,8 +312,108 @@ impl<T> Vector<T> {
             l: 4,
             upper_limit: 16,
             lower_limit: 0,
+            min_k: 2,
             count: 0,
             index: vec![],
+            #[cfg(feature = "metrics")]
+            stats: AllocStats::default(),
+            alloc,
+        }
+    }
+
+    /// Return an empty vector with blocks already allocated, using the given
+    /// allocator, to hold at least `n` elements, using the smallest block size
+    /// that can do so.
+    ///
+    /// # Time complexity
+    ///
+    /// O(√n)
+    pub fn with_capacity_in(n: usize, alloc: A) -> Self {
+        let mut vector = Self::new_in(alloc);
+        if n == 0 {
+            return vector;
+        }
+        let mut k = 2;
+        while (1 << k) * (1 << k) < n {
+            k += 1;
+        }
+        vector.set_exponent(k);
+        for _ in 0..n.div_ceil(vector.l) {
+            let block = vector.alloc_block(vector.l);
+            vector.index.push(block);
+        }
+        vector
+    }
+
+    /// Returns a reference to the allocator used for the blocks.
+    pub fn allocator(&self) -> &A {
+        &self.alloc
+    }
+
+    /// Returns the block allocation counters for this vector.
+    #[cfg(feature = "metrics")]
+    pub fn alloc_stats(&self) -> AllocStats {
+        self.stats
+    }
+
+    /// Record the allocation of `blocks` blocks with room for `capacity`
+    /// elements each.
+    #[cfg_attr(not(feature = "metrics"), allow(unused_variables))]
+    fn record_allocs(&mut self, blocks: usize, capacity: usize) {
+        #[cfg(feature = "metrics")]
+        {
+            self.stats.allocs += blocks;
+            self.stats.bytes_allocated += blocks * capacity * core::mem::size_of::<T>();
+        }
+    }
+
+    /// Record the deallocation of `blocks` blocks.
+    #[cfg_attr(not(feature = "metrics"), allow(unused_variables))]
+    fn record_deallocs(&mut self, blocks: usize) {
+        #[cfg(feature = "metrics")]
+        {
+            self.stats.deallocs += blocks;
+        }
+    }
+
+    /// Allocate a block with room for `capacity` elements on behalf of this
+    /// vector.
+    fn alloc_block(&mut self, capacity: usize) -> CyclicArray<T, A> {
+        self.record_allocs(1, capacity);
+        CyclicArray::new_in(capacity, self.alloc.clone())
+    }
+
+    /// Deallocate a block that was held by this vector, dropping any elements
+    /// that remain in it.
+    fn dealloc_block(&mut self, block: CyclicArray<T, A>) {
+        self.record_deallocs(1);
+        drop(block);
+    }
+
+    /// Add the block allocation counters of `other` to those of this vector
+    /// and reset the counters of `other`, as when taking over its blocks.
+    #[cfg_attr(not(feature = "metrics"), allow(unused_variables))]
+    fn take_stats_from(&mut self, other: &mut Vector<T, A>) {
+        #[cfg(feature = "metrics")]
+        {
+            let theirs = core::mem::take(&mut other.stats);
+            self.stats.allocs += theirs.allocs;
+            self.stats.deallocs += theirs.deallocs;
+            self.stats.bytes_allocated += theirs.bytes_allocated;
+        }
+    }
+
+    /// Move the allocation counts of `blocks` of the blocks of this vector to
+    /// `other`, as when handing those blocks over to it.
+    #[cfg_attr(not(feature = "metrics"), allow(unused_variables))]
+    fn give_stats_to(&mut self, other: &mut Vector<T, A>, blocks: usize) {
+        #[cfg(feature = "metrics")]
+        {
+            let bytes = blocks * self.l * core::mem::size_of::<T>();
+            self.stats.allocs = self.stats.allocs.saturating_sub(blocks);
+            self.stats.bytes_allocated = self.stats.bytes_allocated.saturating_sub(bytes);
+            other.stats.allocs += blocks;
+            other.stats.bytes_allocated += bytes;
         }
     }
 
@@ -76,16 +421,24 @@ impl<T> Vector<T> {
     /// deques of double the capacity.
     fn expand(&mut self) {
         let l_prime = 1 << (self.k + 1);
-        let old_index: Vec<CyclicArray<T>> = std::mem::take(&mut self.index);
+        let old_index: Vec<CyclicArray<T, A>> = core::mem::take(&mut self.index);
         let mut iter = old_index.into_iter();
         while let Some(a) = iter.next() {
             if let Some(b) = iter.next() {
                 self.index.push(CyclicArray::combine(a, b));
+                self.record_deallocs(2);
             } else {
                 self.index.push(CyclicArray::from(l_prime, a));
+                self.record_deallocs(1);
             }
+            self.record_allocs(1, l_prime);
         }
-        self.k += 1;
+        self.set_exponent(self.k + 1);
+    }
+
+    /// Set the block size to 2^k and update the dependent fields accordingly.
+    fn set_exponent(&mut self, k: usize) {
+        self.k = k;
         self.k_mask = (1 << self.k) - 1;
         self.l = 1 << self.k;
         self.upper_limit = self.l * self.l;
@@ -95,15 +448,21 @@ impl<T> Vector<T> {
     /// Inserts an element at position `index` within the array, shifting some
     /// elements to the right as needed.
     pub fn insert(&mut self, index: usize, value: T) {
+        self.insert_with(index, value, true);
+    }
+
+    /// Inserts an element at position `index`, expanding the vector as needed
+    /// only if `rebalance` is true, otherwise simply adding another block.
+    fn insert_with(&mut self, index: usize, value: T, rebalance: bool) {
         let len = self.count;
         if index > len {
             panic!("insertion index (is {index}) should be <= len (is {len})");
         }
-        if len >= self.upper_limit {
-            self.expand();
-        }
-        if len >= self.capacity() {
-            self.index.push(CyclicArray::<T>::new(self.l));
+        if rebalance {
+            self.reserve(1);
+        } else if len >= self.capacity() {
+            let block = self.alloc_block(self.l);
+            self.index.push(block);
         }
         let sub = index >> self.k;
         let end = len >> self.k;
@@ -136,6 +495,19 @@ impl<T> Vector<T> {
         self.insert(self.count, value);
     }
 
+    /// Prepends an element to the front of a collection.
+    ///
+    /// # Panics
+    ///
+    /// Panics if a new block is allocated that would exceed `isize::MAX` _bytes_.
+    ///
+    /// # Time complexity
+    ///
+    /// O(√N) in the worst case.
+    pub fn push_front(&mut self, value: T) {
+        self.insert(0, value);
+    }
+
     /// Appends an element if there is sufficient spare capacity, otherwise an
     /// error is returned with the element.
     ///
@@ -151,6 +523,176 @@ impl<T> Vector<T> {
         }
     }
 
+    /// Appends elements from the iterator until the spare capacity has been
+    /// exhausted, then returns the partially consumed iterator so that the
+    /// caller may handle the remaining elements.
+    ///
+    /// # Time complexity
+    ///
+    /// O(m) where m is the number of elements appended.
+    pub fn push_until_full<I: IntoIterator<Item = T>>(&mut self, iter: I) -> I::IntoIter {
+        let mut iter = iter.into_iter();
+        while self.count < self.capacity() {
+            let Some(value) = iter.next() else {
+                break;
+            };
+            self.push(value);
+        }
+        iter
+    }
+
+    /// Reserves capacity for at least `additional` more elements by expanding
+    /// the vector and allocating blocks as needed, such that adding that many
+    /// elements afterward will neither allocate nor rebuild the blocks. Does
+    /// nothing if the capacity is already sufficient.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the new capacity overflows `usize`.
+    ///
+    /// # Time complexity
+    ///
+    /// O(N) if the vector must be expanded, otherwise O(√N) for the blocks
+    /// allocated. When used in place of repeated expansion during insertion,
+    /// the cost is amortized over the elements added.
+    pub fn reserve(&mut self, additional: usize) {
+        let wanted = self
+            .count
+            .checked_add(additional)
+            .expect("capacity overflow");
+        self.expand_to_hold(wanted);
+        while self.capacity() < wanted {
+            let block = self.alloc_block(self.l);
+            self.index.push(block);
+        }
+    }
+
+    /// Expand the vector as many times as needed for `wanted` elements to fit
+    /// within its limit, without allocating any additional blocks.
+    fn expand_to_hold(&mut self, wanted: usize) {
+        while wanted > self.upper_limit {
+            if self.index.is_empty() {
+                // nothing to rebuild, simply enlarge the blocks
+                self.set_exponent(self.k + 1);
+            } else {
+                self.expand();
+            }
+        }
+    }
+
+    /// Reserves room for at least `elements` more elements in the form of empty
+    /// blocks at the end of the vector, rounding up to a whole number of
+    /// blocks. The block size is increased only if the number of blocks would
+    /// otherwise exceed the block size.
+    ///
+    /// # Time complexity
+    ///
+    /// O(N) if the vector must be expanded, otherwise O(√N) for the blocks
+    /// allocated.
+    pub fn reserve_blocks_for(&mut self, elements: usize) {
+        loop {
+            let wanted = elements.div_ceil(self.l);
+            let empty = self.index.iter().rev().take_while(|b| b.is_empty()).count();
+            let blocks = self.index.len() - empty + wanted;
+            if blocks <= self.l {
+                for _ in empty..wanted {
+                    let block = self.alloc_block(self.l);
+                    self.index.push(block);
+                }
+                break;
+            } else if self.index.is_empty() {
+                self.set_exponent(self.k + 1);
+            } else {
+                self.expand();
+            }
+        }
+    }
+
+    /// Grows the length of the vector by `additional`, filling the new slots
+    /// with the default value of the element type.
+    ///
+    /// # Time complexity
+    ///
+    /// O(m) where m is the number of elements added, unless the vector must be
+    /// expanded, which is O(N).
+    pub fn reserve_initialized(&mut self, additional: usize)
+    where
+        T: Default,
+    {
+        self.reserve(additional);
+        for _ in 0..additional {
+            self.push(T::default());
+        }
+    }
+
+    /// Allocates enough blocks to hold at least `additional` more elements and
+    /// returns the uninitialized slots that follow the last element as a
+    /// sequence of physical runs (pointer and slot count), in logical order.
+    /// The runs cover exactly `additional` slots.
+    ///
+    /// Once the slots have been written, call `commit_len()` to make the new
+    /// elements part of the vector.
+    ///
+    /// # Safety
+    ///
+    /// The returned pointers are invalidated by any other operation on the
+    /// vector prior to calling `commit_len()`. Elements must be written with
+    /// `std::ptr::write` (or equivalent) as the slots hold no valid values.
+    ///
+    /// # Time complexity
+    ///
+    /// O(√N) unless the vector must be expanded, which is O(N).
+    pub unsafe fn reserve_uninit(&mut self, additional: usize) -> Vec<(*mut T, usize)> {
+        self.reserve(additional);
+        let mut runs: Vec<(*mut T, usize)> = Vec::new();
+        let mut remaining = additional;
+        let mut block = self.count >> self.k;
+        while remaining > 0 {
+            for (ptr, len) in self.index[block].spare_runs() {
+                let take = len.min(remaining);
+                if take > 0 {
+                    runs.push((ptr, take));
+                    remaining -= take;
+                }
+            }
+            block += 1;
+        }
+        runs
+    }
+
+    /// Sets the length of the vector to `new_count` following a call to
+    /// `reserve_uninit()`.
+    ///
+    /// # Safety
+    ///
+    /// The caller must have initialized exactly the slots between the current
+    /// length and `new_count`, in the order given by the runs returned from
+    /// `reserve_uninit()`. Uninitialized slots that are committed will be
+    /// treated as valid elements, and initialized slots beyond `new_count` will
+    /// be leaked.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `new_count` is less than the current length or greater than
+    /// the capacity.
+    pub unsafe fn commit_len(&mut self, new_count: usize) {
+        let len = self.count;
+        if new_count < len {
+            panic!("new length (is {new_count}) should be >= len (is {len})");
+        }
+        let capacity = self.capacity();
+        if new_count > capacity {
+            panic!("new length (is {new_count}) should be <= capacity (is {capacity})");
+        }
+        let mut block = len >> self.k;
+        while (block << self.k) < new_count {
+            let block_len = (new_count - (block << self.k)).min(self.l);
+            unsafe { self.index[block].set_len(block_len) };
+            block += 1;
+        }
+        self.count = new_count;
+    }
+
     /// Retrieve a reference to the element at the given offset.
     ///
     /// # Time complexity
@@ -166,6 +708,21 @@ impl<T> Vector<T> {
         }
     }
 
+    /// Returns the block that holds the element at the given offset along with
+    /// the offset of the element within that block, or `None` if the offset is
+    /// out of bounds.
+    ///
+    /// # Time complexity
+    ///
+    /// Constant time.
+    pub fn block_of(&self, index: usize) -> Option<(&CyclicArray<T, A>, usize)> {
+        if index >= self.count {
+            None
+        } else {
+            Some((&self.index[index >> self.k], index & self.k_mask))
+        }
+    }
+
     /// Returns a mutable reference to an element.
     ///
     /// # Time complexity
@@ -181,20 +738,143 @@ impl<T> Vector<T> {
         }
     }
 
+    /// Returns a reference to the first element, or `None` if empty.
+    ///
+    /// # Time complexity
+    ///
+    /// Constant time.
+    pub fn first(&self) -> Option<&T> {
+        self.get(0)
+    }
+
+    /// Returns a mutable reference to the first element, or `None` if empty.
+    ///
+    /// # Time complexity
+    ///
+    /// Constant time.
+    pub fn first_mut(&mut self) -> Option<&mut T> {
+        self.get_mut(0)
+    }
+
+    /// Returns a reference to the last element, or `None` if empty.
+    ///
+    /// # Time complexity
+    ///
+    /// Constant time.
+    pub fn last(&self) -> Option<&T> {
+        self.get(self.count.wrapping_sub(1))
+    }
+
+    /// Returns a mutable reference to the last element, or `None` if empty.
+    ///
+    /// # Time complexity
+    ///
+    /// Constant time.
+    pub fn last_mut(&mut self) -> Option<&mut T> {
+        self.get_mut(self.count.wrapping_sub(1))
+    }
+
+    /// Returns an entry for the element at the given offset, allowing it to be
+    /// read and modified with a single lookup, or `None` if out of bounds.
+    ///
+    /// # Time complexity
+    ///
+    /// Constant time.
+    pub fn entry(&mut self, index: usize) -> Option<Entry<'_, T>> {
+        self.get_mut(index).map(|value| Entry { value })
+    }
+
     /// Shrink the capacity of this vector by splitting its deques into new
-    /// deques of half the capacity.
+    /// deques of half the capacity. Empty deques at the end are released
+    /// rather than split.
     fn compress(&mut self) {
-        let old_index: Vec<CyclicArray<T>> = std::mem::take(&mut self.index);
+        self.trim_empty_blocks();
+        let old_index: Vec<CyclicArray<T, A>> = core::mem::take(&mut self.index);
         for old_deque in old_index.into_iter() {
             let (a, b) = old_deque.split();
             self.index.push(a);
             self.index.push(b);
+            self.record_allocs(2, self.l / 2);
+            self.record_deallocs(1);
         }
-        self.k -= 1;
-        self.k_mask = (1 << self.k) - 1;
-        self.l = 1 << self.k;
-        self.upper_limit = self.l * self.l;
-        self.lower_limit = self.upper_limit / 8;
+        self.set_exponent(self.k - 1);
+    }
+
+    /// Rebuilds the vector using blocks of size 2^k, preserving the order of
+    /// the elements.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `k` is less than 2, or if the elements would not fit
+    /// within 2^k blocks of that size.
+    ///
+    /// # Time complexity
+    ///
+    /// O(n)
+    pub fn reshape_to_exponent(&mut self, k: usize) -> Result<(), ReshapeError> {
+        if k < 2 {
+            return Err(ReshapeError::ExponentTooSmall);
+        }
+        if k >= usize::BITS as usize / 2 {
+            return Err(ReshapeError::ExponentTooLarge);
+        }
+        let l = 1 << k;
+        if self.count > l * l {
+            return Err(ReshapeError::TooManyElements);
+        }
+        let old_index: Vec<CyclicArray<T, A>> = core::mem::take(&mut self.index);
+        for mut old_deque in old_index.into_iter() {
+            while let Some(value) = old_deque.pop_front() {
+                if self.index.last().is_none_or(|b| b.is_full()) {
+                    let block = self.alloc_block(l);
+                    self.index.push(block);
+                }
+                self.index.last_mut().unwrap().push_back(value);
+            }
+            self.dealloc_block(old_deque);
+        }
+        self.set_exponent(k);
+        Ok(())
+    }
+
+    /// Runs the given function with a guard through which elements may be
+    /// inserted and removed without the vector expanding or compressing, then
+    /// rebuilds the vector at most once to restore a suitable block size.
+    ///
+    /// This avoids repeated rebuilds while performing many operations, at the
+    /// cost of less efficient operations within the batch.
+    pub fn batch<R, F>(&mut self, f: F) -> R
+    where
+        F: FnOnce(&mut BatchGuard<'_, T, A>) -> R,
+    {
+        let mut guard = BatchGuard { vector: self };
+        let result = f(&mut guard);
+        self.rebalance();
+        result
+    }
+
+    /// Rebuild the vector using the smallest block size that can hold the
+    /// elements, but only if the length has moved beyond the limits at which
+    /// the vector would have been expanded or compressed.
+    fn rebalance(&mut self) {
+        let compressible = self.count < self.lower_limit && self.k > self.min_k;
+        if self.count <= self.upper_limit && !compressible {
+            return;
+        }
+        let mut k = self.min_k;
+        while (1 << k) * (1 << k) < self.count {
+            k += 1;
+        }
+        if k != self.k {
+            self.reshape_to_exponent(k)
+                .expect("block exponent is suitable for the length");
+        }
+    }
+
+    /// Returns the exponent k for the size of the blocks, which hold 2^k
+    /// elements each.
+    pub fn block_exponent(&self) -> usize {
+        self.k
     }
 
     /// Removes an element from position `index` within the array, shifting some
@@ -204,12 +884,18 @@ impl<T> Vector<T> {
     ///
     /// O(√N) in the worst case.
     pub fn remove(&mut self, index: usize) -> T {
+        self.remove_with(index, true)
+    }
+
+    /// Removes an element from position `index`, compressing the vector as
+    /// needed only if `rebalance` is true.
+    fn remove_with(&mut self, index: usize, rebalance: bool) -> T {
         let len = self.count;
         if index > len {
             panic!("removal index (is {index}) should be <= len (is {len})");
         }
-        // avoid compressing to deques smaller than 4
-        if len < self.lower_limit && self.k > 2 {
+        // avoid compressing to deques smaller than the minimum
+        if rebalance && len < self.lower_limit && self.k > self.min_k {
             self.compress();
         }
         let sub = index >> self.k;
@@ -229,12 +915,35 @@ impl<T> Vector<T> {
         }
         if self.index[end].is_empty() {
             // prune circular arrays as they become empty
-            self.index.pop();
+            let block = self.index.pop().unwrap();
+            self.dealloc_block(block);
         }
         self.count -= 1;
         ret
     }
 
+    /// Removes an element from position `index` and returns it, replacing it
+    /// with the last element of the vector. This does not preserve the order
+    /// of the elements, but avoids shifting the elements that follow.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `index` is out of bounds.
+    ///
+    /// # Time complexity
+    ///
+    /// O(√N) in the worst case, as with `pop()`.
+    pub fn swap_remove(&mut self, index: usize) -> T {
+        let len = self.count;
+        if index >= len {
+            panic!("swap_remove index (is {index}) should be < len (is {len})");
+        }
+        if index != len - 1 {
+            self.swap(index, len - 1);
+        }
+        self.remove(len - 1)
+    }
+
     /// Removes the last element from the vector and returns it, or `None` if the
     /// vector is empty.
     ///
@@ -249,802 +958,6838 @@ impl<T> Vector<T> {
         }
     }
 
-    /// Removes and returns the last element from a vector if the predicate
-    /// returns true, or `None`` if the predicate returns `false`` or the vector
-    /// is empty (the predicate will not be called in that case).
+    /// Removes the first element from the vector and returns it, or `None` if
+    /// the vector is empty.
     ///
     /// # Time complexity
     ///
     /// O(√N) in the worst case.
-    pub fn pop_if(&mut self, predicate: impl FnOnce(&mut T) -> bool) -> Option<T> {
-        if self.count == 0 {
-            None
-        } else if let Some(last) = self.get_mut(self.count - 1) {
-            if predicate(last) { self.pop() } else { None }
+    pub fn pop_front(&mut self) -> Option<T> {
+        if self.count > 0 {
+            Some(self.remove(0))
         } else {
             None
         }
     }
 
-    // Returns an iterator over the vector.
-    //
-    // The iterator yields all items from start to end.
-    pub fn iter(&self) -> VectorIter<'_, T> {
-        VectorIter {
-            array: self,
-            index: 0,
+    /// Removes up to `n` elements from the end of the vector and returns them
+    /// in their original order. Fewer than `n` elements are returned if the
+    /// vector is shorter than that.
+    ///
+    /// # Time complexity
+    ///
+    /// O(n) for the elements removed.
+    pub fn pop_back_n_vec(&mut self, n: usize) -> Vec<T> {
+        let n = n.min(self.count);
+        let mut popped: Vec<T> = Vec::with_capacity(n);
+        for _ in 0..n {
+            if let Some(value) = self.pop() {
+                popped.push(value);
+            }
         }
+        popped.reverse();
+        popped
     }
 
-    /// Return the number of elements in the vector.
+    /// Shortens the vector to the first `len` elements, dropping the rest from
+    /// the tail blocks and releasing any blocks that become empty, then
+    /// compressing the vector if it has become sparse. Has no effect if `len`
+    /// is greater than or equal to the length of the vector.
     ///
     /// # Time complexity
     ///
-    /// Constant time.
-    pub fn len(&self) -> usize {
-        self.count
+    /// O(m) for the m elements dropped, or O(√N) if the elements do not need
+    /// to be dropped.
+    pub fn truncate(&mut self, len: usize) {
+        while self.count > len {
+            let Some(last) = self.index.last_mut() else {
+                break;
+            };
+            let dropping = last.len().min(self.count - len);
+            if core::mem::needs_drop::<T>() {
+                for _ in 0..dropping {
+                    last.pop_back();
+                }
+            } else {
+                unsafe { last.set_len(last.len() - dropping) };
+            }
+            self.count -= dropping;
+            if last.is_empty() {
+                let block = self.index.pop().unwrap();
+                self.dealloc_block(block);
+            }
+        }
+        self.compress_to_fit();
+        self.trim_empty_blocks();
     }
 
-    /// Returns the total number of elements the vector can hold without
-    /// reallocating.
+    /// Compress the vector as many times as needed for its length to be no
+    /// less than the lower limit, or until the minimum block size is reached.
+    fn compress_to_fit(&mut self) {
+        if self.count < self.lower_limit && self.k > self.min_k {
+            while self.count < self.lower_limit && self.k > self.min_k {
+                self.compress();
+            }
+            // splitting the last block may have left an empty block
+            self.trim_empty_blocks();
+        }
+    }
+
+    /// Truncates the vector to end just before the first element for which
+    /// the predicate returns `true`, returning whether such an element was
+    /// found. The vector is unchanged if no element matches.
     ///
     /// # Time complexity
     ///
-    /// Constant time.
-    pub fn capacity(&self) -> usize {
-        (1 << self.k) * self.index.len()
+    /// O(n)
+    pub fn truncate_at<F: FnMut(&T) -> bool>(&mut self, f: F) -> bool {
+        match self.iter().position(f) {
+            Some(index) => {
+                self.truncate(index);
+                true
+            }
+            None => false,
+        }
     }
 
-    /// Returns true if the array has a length of 0.
+    /// Removes the longest run of elements at the end of the vector that are
+    /// equal to `value`, returning the number of elements removed.
     ///
     /// # Time complexity
     ///
-    /// Constant time.
-    pub fn is_empty(&self) -> bool {
-        self.count == 0
+    /// O(m) for the m elements removed.
+    pub fn trim_end_value(&mut self, value: &T) -> usize
+    where
+        T: PartialEq,
+    {
+        let mut len = self.count;
+        while len > 0 && self[len - 1] == *value {
+            len -= 1;
+        }
+        let removed = self.count - len;
+        self.truncate(len);
+        removed
     }
 
-    /// Clears the vector, removing all values and deallocating all blocks.
+    /// Removes and returns the last element from a vector if the predicate
+    /// returns true, or `None`` if the predicate returns `false`` or the vector
+    /// is empty (the predicate will not be called in that case).
     ///
     /// # Time complexity
     ///
-    /// O(n) if elements are droppable, otherwise O(√N)
-    pub fn clear(&mut self) {
-        self.index.clear();
-        self.count = 0;
-        self.k = 2;
-        self.k_mask = 3;
-        self.l = 1 << self.k;
-        self.upper_limit = self.l * self.l;
-        self.lower_limit = self.upper_limit / 8;
+    /// O(√N) in the worst case.
+    pub fn pop_if(&mut self, predicate: impl FnOnce(&mut T) -> bool) -> Option<T> {
+        if let Some(last) = self.last_mut() {
+            if predicate(last) { self.pop() } else { None }
+        } else {
+            None
+        }
     }
-}
 
-impl<T> Default for Vector<T> {
-    fn default() -> Self {
-        Self::new()
+    /// Returns true if the vector contains an element equal to `x`.
+    ///
+    /// # Time complexity
+    ///
+    /// O(n)
+    pub fn contains(&self, x: &T) -> bool
+    where
+        T: PartialEq,
+    {
+        self.iter().any(|value| value == x)
     }
-}
 
-impl<T> fmt::Display for Vector<T> {
-    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        write!(
-            f,
-            "Vector(k: {}, count: {}, dope: {})",
-            self.k,
-            self.count,
-            self.index.len(),
-        )
+    /// Returns the index at which `needle` first occurs as a contiguous run of
+    /// elements, or `None` if it does not occur. An empty `needle` is found at
+    /// index 0.
+    ///
+    /// # Time complexity
+    ///
+    /// O(n * m) where m is the length of the needle.
+    pub fn find_subslice(&self, needle: &[T]) -> Option<usize>
+    where
+        T: PartialEq,
+    {
+        if needle.len() > self.count {
+            return None;
+        }
+        (0..=(self.count - needle.len())).find(|&start| {
+            needle
+                .iter()
+                .enumerate()
+                .all(|(offset, value)| self.get(start + offset) == Some(value))
+        })
     }
-}
 
-impl<T> Index<usize> for Vector<T> {
-    type Output = T;
+    /// Consumes this vector and `other`, both of which must be sorted, and
+    /// returns a single sorted vector containing the elements of both. The
+    /// merge is stable: elements of `self` precede equal elements of `other`.
+    ///
+    /// # Time complexity
+    ///
+    /// O(n + m) where m is the length of the other vector.
+    pub fn merge_sorted(self, other: Vector<T, A>) -> Vector<T, A>
+    where
+        T: Ord,
+    {
+        let mut merged: Vector<T, A> = Vector::new_in(self.alloc.clone());
+        merged.reserve(self.count + other.count);
+        let mut left = self.into_iter().peekable();
+        let mut right = other.into_iter().peekable();
+        loop {
+            let take_left = match (left.peek(), right.peek()) {
+                (Some(a), Some(b)) => a <= b,
+                (Some(_), None) => true,
+                (None, Some(_)) => false,
+                (None, None) => break,
+            };
+            let value = if take_left { left.next() } else { right.next() };
+            merged.push(value.unwrap());
+        }
+        merged
+    }
 
-    fn index(&self, index: usize) -> &Self::Output {
-        let Some(item) = self.get(index) else {
-            panic!("index out of bounds: {}", index);
-        };
-        item
+    /// Returns the smallest ratio of elements to capacity among all blocks
+    /// other than the last, or `None` if there are fewer than two blocks.
+    ///
+    /// All but the last block are always full, hence anything less than 1.0
+    /// indicates that the layout of the vector has been corrupted.
+    ///
+    /// # Time complexity
+    ///
+    /// O(√N)
+    pub fn min_block_fill(&self) -> Option<f64> {
+        let (_, leading) = self.index.split_last()?;
+        leading
+            .iter()
+            .map(|block| block.len() as f64 / block.capacity() as f64)
+            .min_by(f64::total_cmp)
     }
-}
 
-impl<T> IndexMut<usize> for Vector<T> {
-    fn index_mut(&mut self, index: usize) -> &mut Self::Output {
-        let Some(item) = self.get_mut(index) else {
-            panic!("index out of bounds: {}", index);
+    /// Returns the current tier parameters of the vector, including the number
+    /// of blocks it would ideally use for its length.
+    ///
+    /// # Time complexity
+    ///
+    /// Constant time.
+    pub fn tier_info(&self) -> TierInfo {
+        let block_count = self.index.len();
+        let fill_ratio = if block_count == 0 {
+            0.0
+        } else {
+            self.count as f64 / (block_count * self.l) as f64
         };
-        item
+        TierInfo {
+            k: self.k,
+            block_size: self.l,
+            block_count,
+            ideal_block_count: self.count.div_ceil(self.l),
+            fill_ratio,
+        }
     }
-}
 
-impl<A> FromIterator<A> for Vector<A> {
-    fn from_iter<T: IntoIterator<Item = A>>(iter: T) -> Self {
-        let mut arr: Vector<A> = Vector::new();
-        for value in iter {
-            arr.push(value)
-        }
-        arr
+    /// Returns the number of bytes allocated by the vector, which is that of
+    /// the dope vector plus the buffers of each of the blocks.
+    ///
+    /// # Time complexity
+    ///
+    /// O(√N)
+    pub fn memory_usage(&self) -> usize {
+        let dope = self.index.capacity() * core::mem::size_of::<CyclicArray<T, A>>();
+        let blocks: usize = self.index.iter().map(|block| block.capacity()).sum();
+        dope + blocks * core::mem::size_of::<T>()
     }
-}
 
-/// Immutable array iterator.
-pub struct VectorIter<'a, T> {
-    array: &'a Vector<T>,
-    index: usize,
-}
+    /// Returns the number of allocated bytes that are not holding elements,
+    /// including the unused slots in the blocks and in the dope vector.
+    ///
+    /// # Time complexity
+    ///
+    /// O(√N)
+    pub fn overhead(&self) -> usize {
+        let dope = self.index.capacity() - self.index.len();
+        let blocks: usize = self.index.iter().map(|block| block.capacity()).sum();
+        dope * core::mem::size_of::<CyclicArray<T, A>>()
+            + (blocks - self.count) * core::mem::size_of::<T>()
+    }
 
-impl<'a, T> Iterator for VectorIter<'a, T> {
-    type Item = &'a T;
+    /// Clones and appends all of the elements in the slice to the vector.
+    fn extend_from_slice(&mut self, other: &[T])
+    where
+        T: Clone,
+    {
+        for value in other {
+            self.push(value.clone());
+        }
+    }
 
-    fn next(&mut self) -> Option<Self::Item> {
-        let value = self.array.get(self.index);
-        self.index += 1;
-        value
+    /// Exchanges the contents of this vector with those of `other`, such that
+    /// each ends up holding the elements of the other, in order.
+    ///
+    /// The blocks of this vector are reused if the length of `other` would
+    /// neither expand nor compress the vector, otherwise they are released and
+    /// the vector is rebuilt with a block size suited to that length. Blocks
+    /// left empty afterward are released as well.
+    ///
+    /// # Time complexity
+    ///
+    /// O(n + m) where m is the length of the other vector.
+    pub fn swap_with_vec(&mut self, other: &mut Vec<T>) {
+        let ours = self.take_elements();
+        let theirs = core::mem::replace(other, ours);
+        let len = theirs.len();
+        if len > self.upper_limit || (len < self.lower_limit && self.k > self.min_k) {
+            self.clear();
+        }
+        self.reserve(len);
+        self.refill(theirs);
+        self.trim_empty_blocks();
     }
-}
 
-impl<T> IntoIterator for Vector<T> {
-    type Item = T;
-    type IntoIter = VectorIntoIter<Self::Item>;
+    /// Returns a new vector containing clones of the elements of this vector
+    /// that do not appear in `other`, in their original order.
+    ///
+    /// # Time complexity
+    ///
+    /// O(n + m) where m is the length of the other vector.
+    #[cfg(feature = "std")]
+    pub fn difference(&self, other: &Vector<T, A>) -> Vector<T, A>
+    where
+        T: Hash + Eq + Clone,
+    {
+        let theirs: HashSet<&T> = other.iter().collect();
+        let mut result = Vector::new_in(self.alloc.clone());
+        result.extend(self.iter().filter(|v| !theirs.contains(v)).cloned());
+        result
+    }
 
-    fn into_iter(self) -> Self::IntoIter {
-        let mut me = std::mem::ManuallyDrop::new(self);
-        let index = std::mem::take(&mut me.index);
-        VectorIntoIter {
-            count: me.count,
-            index,
-        }
+    /// Returns a new vector containing clones of the elements of this vector
+    /// that also appear in `other`, in their original order.
+    ///
+    /// # Time complexity
+    ///
+    /// O(n + m) where m is the length of the other vector.
+    #[cfg(feature = "std")]
+    pub fn intersection(&self, other: &Vector<T, A>) -> Vector<T, A>
+    where
+        T: Hash + Eq + Clone,
+    {
+        let theirs: HashSet<&T> = other.iter().collect();
+        let mut result = Vector::new_in(self.alloc.clone());
+        result.extend(self.iter().filter(|v| theirs.contains(v)).cloned());
+        result
     }
-}
 
-/// An iterator that moves out of a tiered vector.
-pub struct VectorIntoIter<T> {
-    /// number of remaining elements
-    count: usize,
-    /// index of circular deques
-    index: Vec<CyclicArray<T>>,
-}
+    /// Returns a new vector containing clones of all the elements of this
+    /// vector followed by the elements of `other` that do not appear in this
+    /// vector, each in their original order.
+    ///
+    /// # Time complexity
+    ///
+    /// O(n + m) where m is the length of the other vector.
+    #[cfg(feature = "std")]
+    pub fn union(&self, other: &Vector<T, A>) -> Vector<T, A>
+    where
+        T: Hash + Eq + Clone,
+    {
+        let ours: HashSet<&T> = self.iter().collect();
+        let mut result = Vector::new_in(self.alloc.clone());
+        result.extend(
+            self.iter()
+                .chain(other.iter().filter(|v| !ours.contains(v)))
+                .cloned(),
+        );
+        result
+    }
 
-impl<T> Iterator for VectorIntoIter<T> {
-    type Item = T;
+    /// Consumes the vector and returns an iterator that yields the elements
+    /// from last to first, as if popped from a stack. Elements may also be
+    /// taken from the front via `next_back()`.
+    ///
+    /// Any elements that remain when the iterator is dropped will be dropped.
+    pub fn into_iter_back(self) -> core::iter::Rev<VectorIntoIter<T, A>> {
+        self.into_iter().rev()
+    }
 
-    fn next(&mut self) -> Option<Self::Item> {
-        if self.count > 0 {
-            let ret = self.index[0].pop_front();
-            self.count -= 1;
-            if self.index[0].is_empty() {
-                self.index.remove(0);
+    /// Exchanges the elements at the two given offsets. Swapping an element
+    /// with itself has no effect.
+    ///
+    /// # Panics
+    ///
+    /// Panics if either offset is out of bounds.
+    ///
+    /// # Time complexity
+    ///
+    /// Constant time.
+    pub fn swap(&mut self, a: usize, b: usize) {
+        if a >= self.count || b >= self.count {
+            panic!("index out of bounds");
+        }
+        if a == b {
+            return;
+        }
+        let pa: *mut T = self.get_mut(a).unwrap();
+        let pb: *mut T = self.get_mut(b).unwrap();
+        // distinct offsets never share a slot, so the pointers do not alias
+        unsafe { core::ptr::swap(pa, pb) }
+    }
+
+    /// Returns mutable references to the elements at each of the given
+    /// offsets, or `None` if any offset is out of bounds or if any two
+    /// offsets are equal.
+    ///
+    /// # Time complexity
+    ///
+    /// O(N²) where N is the number of offsets.
+    pub fn get_disjoint_mut<const N: usize>(&mut self, indices: [usize; N]) -> Option<[&mut T; N]> {
+        for (i, index) in indices.iter().enumerate() {
+            if *index >= self.count || indices[..i].contains(index) {
+                return None;
             }
-            ret
-        } else {
-            None
         }
+        let ptrs: [*mut T; N] = indices.map(|index| self.get_mut(index).unwrap() as *mut T);
+        // the offsets are in bounds and distinct, so no two references alias
+        Some(ptrs.map(|ptr| unsafe { &mut *ptr }))
     }
-}
 
-/// Basic circular buffer, or what Goodrich and Kloss call a circular deque.
-///
-/// This implementation allows push and pop from both ends of the buffer and
-/// supports insert and remove from arbitrary offsets.
-///
-/// Unlike the `VecDeque` in the standard library, this array has a fixed size
-/// and will panic if a push is performed while the array is already full.
-pub struct CyclicArray<T> {
-    /// allocated buffer of size `capacity`
-    buffer: *mut T,
-    /// number of slots allocated in the buffer
-    capacity: usize,
-    /// offset of the first entry
-    head: usize,
-    /// number of elements
-    count: usize,
-}
+    /// Exchanges the `len` elements starting at `a_start` with the `len`
+    /// elements starting at `b_start`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if either range extends beyond the end of the vector, or if the
+    /// two ranges overlap.
+    ///
+    /// # Time complexity
+    ///
+    /// O(len)
+    pub fn swap_ranges(&mut self, a_start: usize, b_start: usize, len: usize) {
+        let count = self.count;
+        for start in [a_start, b_start] {
+            if start.checked_add(len).is_none_or(|end| end > count) {
+                panic!("range {start}..{start}+{len} out of bounds for length {count}");
+            }
+        }
+        if len > 0 && a_start < b_start + len && b_start < a_start + len {
+            panic!("ranges starting at {a_start} and {b_start} with length {len} overlap");
+        }
+        for offset in 0..len {
+            self.swap(a_start + offset, b_start + offset);
+        }
+    }
 
-impl<T> CyclicArray<T> {
-    /// Construct a new cyclic array with the given capacity.
-    pub fn new(capacity: usize) -> Self {
-        let buffer = if capacity == 0 {
-            std::ptr::null_mut::<T>()
-        } else {
-            let layout = Layout::array::<T>(capacity).expect("unexpected overflow");
-            unsafe {
-                let ptr = alloc(layout).cast::<T>();
-                if ptr.is_null() {
-                    handle_alloc_error(layout);
+    /// Reorders the elements such that the element at index `i` is the element
+    /// that was formerly at index `perm[i]`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the length of `perm` differs from the length of the vector.
+    /// In debug builds, also panics if `perm` is not a permutation of the
+    /// indices of the vector. In release builds such a `perm` leaves the
+    /// elements in an unspecified order.
+    ///
+    /// # Time complexity
+    ///
+    /// O(n)
+    pub fn apply_permutation(&mut self, perm: &[usize]) {
+        let len = self.count;
+        if perm.len() != len {
+            panic!(
+                "permutation length (is {}) should be == len (is {len})",
+                perm.len()
+            );
+        }
+        let mut visited: Vec<u64> = vec![0; len.div_ceil(64)];
+        if cfg!(debug_assertions) {
+            for &p in perm {
+                let seen = p < len && visited[p / 64] & (1 << (p % 64)) != 0;
+                assert!(p < len && !seen, "perm is not a permutation of 0..len");
+                visited[p / 64] |= 1 << (p % 64);
+            }
+            visited.fill(0);
+        }
+        for start in 0..len {
+            if visited[start / 64] & (1 << (start % 64)) != 0 {
+                continue;
+            }
+            // follow the cycle, pulling each element into place
+            let mut current = start;
+            loop {
+                visited[current / 64] |= 1 << (current % 64);
+                let source = perm[current];
+                // the start of the cycle is already marked, while any other
+                // marked source means that perm is not a permutation
+                if visited[source / 64] & (1 << (source % 64)) != 0 {
+                    break;
                 }
-                ptr
+                self.swap(current, source);
+                current = source;
             }
-        };
-        Self {
-            buffer,
-            capacity,
-            head: 0,
-            count: 0,
         }
     }
 
-    /// Free the buffer for this cyclic array without dropping the elements.
-    fn dealloc(&mut self) {
-        // apparently this has no effect if capacity is zero
-        let layout = Layout::array::<T>(self.capacity).expect("unexpected overflow");
-        unsafe {
-            dealloc(self.buffer as *mut u8, layout);
-        }
+    /// Returns the index of the first element equal to `target`, or `None` if
+    /// there is no such element.
+    ///
+    /// The contiguous runs of each block are compared several elements at a
+    /// time without branching, allowing the compiler to vectorize the search.
+    ///
+    /// # Time complexity
+    ///
+    /// O(n)
+    pub fn find_copy(&self, target: T) -> Option<usize>
+    where
+        T: Copy + PartialEq,
+    {
+        self.find_in_runs(|run| find_in_slice(run, target))
     }
 
-    /// Take the elements from the two other cyclic arrays into a new cyclic
-    /// array with the combined capacity.
-    pub fn combine(a: CyclicArray<T>, b: CyclicArray<T>) -> Self {
-        let mut this: CyclicArray<T> = CyclicArray::new(a.capacity + b.capacity);
-        let mut this_pos = 0;
-        let their_a = std::mem::ManuallyDrop::new(a);
-        let their_b = std::mem::ManuallyDrop::new(b);
-        for mut other in [their_a, their_b] {
-            if other.head + other.count > other.capacity {
-                // data wraps around, copy as two blocks
-                let src = unsafe { other.buffer.add(other.head) };
-                let dst = unsafe { this.buffer.add(this_pos) };
-                let count_1 = other.capacity - other.head;
-                unsafe { std::ptr::copy(src, dst, count_1) }
-                this_pos += count_1;
-                let dst = unsafe { this.buffer.add(this_pos) };
-                let count_2 = other.count - count_1;
-                unsafe { std::ptr::copy(other.buffer, dst, count_2) }
-                this_pos += count_2;
-            } else {
-                // data is contiguous, copy as one block
-                let src = unsafe { other.buffer.add(other.head) };
-                let dst = unsafe { this.buffer.add(this_pos) };
-                unsafe { std::ptr::copy(src, dst, other.count) }
-                this_pos += other.count;
+    /// Applies the given search to the contiguous runs of each block in turn,
+    /// returning the logical index of the first match.
+    fn find_in_runs<F: FnMut(&[T]) -> Option<usize>>(&self, mut search: F) -> Option<usize> {
+        let mut base = 0;
+        for deque in self.index.iter() {
+            let (first, second) = deque.as_slices();
+            for run in [first, second] {
+                if let Some(offset) = search(run) {
+                    return Some(base + offset);
+                }
+                base += run.len();
             }
-            other.dealloc();
-            this.count += other.count;
         }
-        this
+        None
     }
 
-    /// Take the elements from the other cyclic array into a new cyclic array
-    /// with the given capacity.
-    pub fn from(capacity: usize, other: CyclicArray<T>) -> Self {
-        assert!(capacity > other.count, "capacity cannot be less than count");
-        let layout = Layout::array::<T>(capacity).expect("unexpected overflow");
-        let buffer = unsafe {
-            let ptr = alloc(layout).cast::<T>();
-            if ptr.is_null() {
-                handle_alloc_error(layout);
-            }
-            ptr
-        };
-        let mut them = std::mem::ManuallyDrop::new(other);
-        if them.head + them.count > them.capacity {
-            // data wraps around, copy as two blocks
-            let src = unsafe { them.buffer.add(them.head) };
-            let count_1 = them.capacity - them.head;
-            unsafe { std::ptr::copy(src, buffer, count_1) }
-            let dst = unsafe { buffer.add(count_1) };
-            let count_2 = them.count - count_1;
-            unsafe { std::ptr::copy(them.buffer, dst, count_2) }
-        } else {
-            // data is contiguous, copy as one block
-            let src = unsafe { them.buffer.add(them.head) };
-            unsafe { std::ptr::copy(src, buffer, them.count) }
+    /// Consumes the vector and splits it into `parts` vectors whose lengths
+    /// differ by at most one, with the earliest elements in the first part.
+    /// The blocks are moved into the parts as with `split_off()`, such that
+    /// elements are shifted only for parts that do not begin on a block
+    /// boundary.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `parts` is zero.
+    ///
+    /// # Time complexity
+    ///
+    /// O(parts × √N) if every part begins on a block boundary, otherwise O(n)
+    /// in the worst case.
+    pub fn split_into(self, parts: usize) -> Vec<Vector<T, A>> {
+        if parts == 0 {
+            panic!("number of parts must be greater than zero");
         }
-        them.dealloc();
-        Self {
-            buffer,
-            capacity,
-            head: 0,
-            count: them.count,
+        let quotient = self.count / parts;
+        let remainder = self.count % parts;
+        let mut vector = self;
+        let mut result: Vec<Vector<T, A>> = Vec::with_capacity(parts);
+        // split from the end so that each split moves only the blocks of the
+        // part being split off
+        for part in (1..parts).rev() {
+            let start = part * quotient + part.min(remainder);
+            result.push(vector.split_off(start));
         }
+        result.push(vector);
+        result.reverse();
+        result
     }
 
-    /// Split this cyclic buffer into two equal sized buffers.
+    /// Prepends all of the elements of the iterator such that they appear at
+    /// the front of the vector in iterator order, shifting the existing
+    /// elements to the right only once.
     ///
-    /// The second buffer may be empty if all elements fit within the first
-    /// buffer.
-    pub fn split(self) -> (CyclicArray<T>, CyclicArray<T>) {
-        assert!(
-            self.capacity.is_multiple_of(2),
-            "capacity must be an even number"
-        );
-        let half = self.capacity / 2;
-        let mut me = std::mem::ManuallyDrop::new(self);
-        let mut a: CyclicArray<T> = CyclicArray::new(half);
-        let mut b: CyclicArray<T> = CyclicArray::new(half);
-        let mut remaining = me.count;
-        for other in [&mut a, &mut b] {
-            let mut other_pos = 0;
-            while remaining > 0 && !other.is_full() {
-                let want_to_copy = if me.head + remaining > me.capacity {
-                    me.capacity - me.head
-                } else {
-                    remaining
-                };
-                let can_fit = other.capacity - other.count;
-                let to_copy = if want_to_copy > can_fit {
-                    can_fit
+    /// Whole blocks of the new elements are inserted at the front of the dope
+    /// vector, reusing any spare blocks, while the remaining new elements are
+    /// pushed onto the front of the first block, with each block passing its
+    /// last elements onto the front of the next.
+    ///
+    /// # Time complexity
+    ///
+    /// O(m + r√N) where m is the number of elements prepended and r is the
+    /// remainder of m divided by the block size.
+    pub fn extend_front<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        let mut values: Vec<T> = iter.into_iter().collect();
+        let added = values.len();
+        if added == 0 {
+            return;
+        }
+        self.expand_to_hold(self.count.checked_add(added).expect("capacity overflow"));
+        let l = self.l;
+        let mut carry = values.split_off(added - added % l);
+        let mut used = self.count.div_ceil(l);
+        if !carry.is_empty() {
+            // shift push-pop phase, moving each existing element at most once
+            let mut spill: Vec<T> = Vec::with_capacity(carry.len());
+            for block in self.index[..used].iter_mut() {
+                while block.len() + carry.len() > l {
+                    spill.push(block.pop_back().unwrap());
+                }
+                while let Some(value) = carry.pop() {
+                    block.push_front(value);
+                }
+                spill.reverse();
+                core::mem::swap(&mut carry, &mut spill);
+            }
+            if !carry.is_empty() {
+                if self.index.len() == used {
+                    let block = self.alloc_block(l);
+                    self.index.push(block);
+                }
+                for value in carry {
+                    self.index[used].push_back(value);
+                }
+                used += 1;
+            }
+        }
+        if !values.is_empty() {
+            let mut values = values.into_iter();
+            let mut leading: Vec<CyclicArray<T, A>> = Vec::with_capacity(added / l);
+            for _ in 0..added / l {
+                let mut block = if self.index.len() > used {
+                    self.index.pop().unwrap()
                 } else {
-                    want_to_copy
+                    self.alloc_block(l)
                 };
-                let src = unsafe { me.buffer.add(me.head) };
-                let dst = unsafe { other.buffer.add(other_pos) };
-                unsafe { std::ptr::copy(src, dst, to_copy) };
-                other_pos += to_copy;
-                other.count += to_copy;
-                me.head = me.physical_add(to_copy);
-                remaining -= to_copy;
+                for value in values.by_ref().take(l) {
+                    block.push_back(value);
+                }
+                leading.push(block);
             }
+            self.index.splice(0..0, leading);
         }
-        me.dealloc();
-        (a, b)
+        self.count += added;
     }
 
-    /// Appends an element to the back of the cyclic array.
+    /// Returns true if the elements are sorted in non-descending order.
     ///
-    /// # Panic
+    /// # Time complexity
     ///
-    /// Panics if the buffer is already full.
-    pub fn push_back(&mut self, value: T) {
-        if self.count == self.capacity {
-            panic!("cyclic array is full")
+    /// O(n)
+    pub fn is_sorted(&self) -> bool
+    where
+        T: PartialOrd,
+    {
+        self.is_sorted_by(|a, b| a <= b)
+    }
+
+    /// Returns true if `compare` returns true for every pair of adjacent
+    /// elements, stopping at the first pair for which it returns false.
+    ///
+    /// # Time complexity
+    ///
+    /// O(n)
+    pub fn is_sorted_by<F: FnMut(&T, &T) -> bool>(&self, mut compare: F) -> bool {
+        let mut iter = self.iter();
+        let Some(mut previous) = iter.next() else {
+            return true;
+        };
+        for current in iter {
+            if !compare(previous, current) {
+                return false;
+            }
+            previous = current;
         }
-        let off = self.physical_add(self.count);
-        unsafe { std::ptr::write(self.buffer.add(off), value) }
-        self.count += 1;
+        true
     }
 
-    /// Prepends an element to the front of the cyclic array.
+    /// Consumes the vector and returns a new vector of the same length in which
+    /// each element has been converted using `From::from`.
     ///
-    /// # Panic
+    /// # Time complexity
     ///
-    /// Panics if the buffer is already full.
-    pub fn push_front(&mut self, value: T) {
-        if self.count == self.capacity {
-            panic!("cyclic array is full")
+    /// O(n)
+    pub fn into_converted<B: From<T>>(self) -> Vector<B, A> {
+        let mut converted: Vector<B, A> = Vector::new_in(self.alloc.clone());
+        converted.reserve(self.count);
+        for value in self {
+            converted.push(B::from(value));
         }
-        self.head = self.physical_sub(1);
-        unsafe { std::ptr::write(self.buffer.add(self.head), value) }
-        self.count += 1;
+        converted
     }
 
-    /// Removes the last element and returns it, or `None` if the cyclic array
-    /// is empty.
-    pub fn pop_back(&mut self) -> Option<T> {
-        if self.count == 0 {
-            None
-        } else {
-            self.count -= 1;
-            let off = self.physical_add(self.count);
-            unsafe { Some(std::ptr::read(self.buffer.add(off))) }
+    /// Gathers the elements into a contiguous buffer, applies the given
+    /// function to that buffer, then moves the elements back into the same
+    /// blocks. The buffer is a temporary allocation of n elements.
+    ///
+    /// Should the function panic, the vector is left empty.
+    fn with_gathered<F: FnOnce(&mut [T])>(&mut self, f: F) {
+        let mut gathered = self.take_elements();
+        f(&mut gathered);
+        self.refill(gathered);
+    }
+
+    /// Moves all of the elements out of the blocks, in order, leaving the
+    /// vector empty while retaining its blocks and block size.
+    fn take_elements(&mut self) -> Vec<T> {
+        let mut gathered: Vec<T> = Vec::with_capacity(self.count);
+        for block in self.index.iter_mut() {
+            while let Some(value) = block.pop_front() {
+                gathered.push(value);
+            }
         }
+        self.count = 0;
+        gathered
     }
 
-    /// Removes the first element and returns it, or `None` if the cyclic array
-    /// is empty.
-    pub fn pop_front(&mut self) -> Option<T> {
-        if self.count == 0 {
-            None
-        } else {
-            let old_head = self.head;
-            self.head = self.physical_add(1);
-            self.count -= 1;
-            unsafe { Some(std::ptr::read(self.buffer.add(old_head))) }
+    /// Moves the given values into the blocks of an empty vector, in order,
+    /// allocating additional blocks only once the existing ones are full.
+    fn refill<I: IntoIterator<Item = T>>(&mut self, values: I) {
+        let mut values = values.into_iter();
+        for block in self.index.iter_mut() {
+            while !block.is_full() {
+                let Some(value) = values.next() else {
+                    return;
+                };
+                block.push_back(value);
+                self.count += 1;
+            }
+        }
+        for value in values {
+            self.push(value);
         }
     }
 
-    /// Inserts an element at position `index` within the array, possibly
-    /// shifting some elements to the left or the right as needed.
-    pub fn insert(&mut self, index: usize, value: T) {
+    /// Sorts the vector in ascending order.
+    ///
+    /// This sort is stable, meaning that equal elements retain their relative
+    /// order. An unstable sort may perform fewer moves but offers no such
+    /// guarantee.
+    ///
+    /// The elements are moved into a temporary buffer of n elements to be
+    /// sorted, then moved back into the vector.
+    ///
+    /// # Time complexity
+    ///
+    /// O(n * log(n))
+    pub fn sort(&mut self)
+    where
+        T: Ord,
+    {
+        self.with_gathered(|values| values.sort());
+    }
+
+    /// Sorts the vector using the given comparator function, retaining the
+    /// relative order of equal elements.
+    ///
+    /// # Time complexity
+    ///
+    /// O(n * log(n))
+    pub fn sort_by<F>(&mut self, compare: F)
+    where
+        F: FnMut(&T, &T) -> core::cmp::Ordering,
+    {
+        self.with_gathered(|values| values.sort_by(compare));
+    }
+
+    /// Sorts the vector using the given key extraction function, retaining the
+    /// relative order of elements with equal keys.
+    ///
+    /// # Time complexity
+    ///
+    /// O(n * log(n))
+    pub fn sort_by_key<K, F>(&mut self, f: F)
+    where
+        F: FnMut(&T) -> K,
+        K: Ord,
+    {
+        self.with_gathered(|values| values.sort_by_key(f));
+    }
+
+    /// Sorts the vector in ascending order without preserving the order of
+    /// equal elements, using a temporary buffer as with `sort()`.
+    ///
+    /// # Time complexity
+    ///
+    /// O(n * log(n))
+    pub fn sort_unstable(&mut self)
+    where
+        T: Ord,
+    {
+        self.with_gathered(|values| values.sort_unstable());
+    }
+
+    /// Sorts the vector using the given comparator function, without
+    /// preserving the order of equal elements.
+    ///
+    /// # Time complexity
+    ///
+    /// O(n * log(n))
+    pub fn sort_unstable_by<F>(&mut self, compare: F)
+    where
+        F: FnMut(&T, &T) -> core::cmp::Ordering,
+    {
+        self.with_gathered(|values| values.sort_unstable_by(compare));
+    }
+
+    /// Sorts the vector using the given key extraction function, without
+    /// preserving the order of elements with equal keys.
+    ///
+    /// # Time complexity
+    ///
+    /// O(n * log(n))
+    pub fn sort_unstable_by_key<K, F>(&mut self, f: F)
+    where
+        F: FnMut(&T) -> K,
+        K: Ord,
+    {
+        self.with_gathered(|values| values.sort_unstable_by_key(f));
+    }
+
+    /// Returns a view of the vector as a matrix stored in row-major order with
+    /// `cols` columns.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `cols` is zero or the length is not a multiple of `cols`.
+    pub fn as_matrix(&self, cols: usize) -> MatrixView<'_, T, A> {
+        check_matrix_cols(self.count, cols);
+        MatrixView { vector: self, cols }
+    }
+
+    /// Returns a mutable view of the vector as a matrix stored in row-major
+    /// order with `cols` columns.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `cols` is zero or the length is not a multiple of `cols`.
+    pub fn as_matrix_mut(&mut self, cols: usize) -> MatrixViewMut<'_, T, A> {
+        check_matrix_cols(self.count, cols);
+        MatrixViewMut { vector: self, cols }
+    }
+
+    /// Groups the elements into chunks of `chunk_size` (the last of which may
+    /// be shorter) and folds each chunk into an accumulator using `f`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `chunk_size` is zero.
+    ///
+    /// # Time complexity
+    ///
+    /// O(n)
+    pub fn fold_chunks<B, F>(&self, chunk_size: usize, init: B, mut f: F) -> B
+    where
+        F: FnMut(B, &[&T]) -> B,
+    {
+        if chunk_size == 0 {
+            panic!("chunk size must be greater than zero");
+        }
+        let mut chunk: Vec<&T> = Vec::with_capacity(chunk_size.min(self.count));
+        let mut acc = init;
+        for value in self.iter() {
+            chunk.push(value);
+            if chunk.len() == chunk_size {
+                acc = f(acc, &chunk);
+                chunk.clear();
+            }
+        }
+        if !chunk.is_empty() {
+            acc = f(acc, &chunk);
+        }
+        acc
+    }
+
+    /// Returns true if both vectors hold equal elements in an identical layout,
+    /// meaning that the block sizes, the number of blocks, and the physical
+    /// placement of every element within each block are all the same.
+    ///
+    /// This is intended for testing operations that affect the layout, as two
+    /// vectors with equal elements may have different layouts.
+    ///
+    /// # Time complexity
+    ///
+    /// O(n)
+    pub fn structurally_eq(&self, other: &Vector<T, A>) -> bool
+    where
+        T: PartialEq,
+    {
+        self.k == other.k
+            && self.count == other.count
+            && self.index.len() == other.index.len()
+            && self.index.iter().zip(other.index.iter()).all(|(a, b)| {
+                a.capacity == b.capacity && a.head == b.head && a.as_slices() == b.as_slices()
+            })
+    }
+
+    /// Moves the blocks of this vector to a background thread where they, and
+    /// the elements they contain, will be dropped. The returned handle may be
+    /// used to wait for that to finish.
+    ///
+    /// # Time complexity
+    ///
+    /// O(√N) for the calling thread.
+    #[cfg(feature = "thread")]
+    pub fn drop_deferred(self) -> DropHandle
+    where
+        T: Send + 'static,
+        A: Send + 'static,
+    {
+        let index = self.index;
+        let handle = std::thread::spawn(move || drop(index));
+        DropHandle { handle }
+    }
+
+    /// Returns the number of element moves that inserting at position `index`
+    /// would perform given the current layout, counting both the elements
+    /// moved between blocks and those shifted within a block. Any expansion of
+    /// the vector is not included. The cost of removing from the same position
+    /// is comparable.
+    ///
+    /// # Time complexity
+    ///
+    /// Constant time.
+    pub fn estimated_move_count(&self, index: usize) -> usize {
         let len = self.count;
         if index > len {
             panic!("insertion index (is {index}) should be <= len (is {len})");
         }
-        if len == self.capacity {
-            panic!("cyclic array is full")
+        let sub = index >> self.k;
+        let end = len >> self.k;
+        let r_prime = index & self.k_mask;
+        let Some(deque) = self.index.get(sub) else {
+            return 0;
+        };
+        if sub < end {
+            // the full block gives up its last element before the shift
+            (end - sub) + deque.insert_moves(r_prime, deque.len() - 1)
+        } else {
+            deque.insert_moves(r_prime, deque.len())
         }
-        //
-        // Some free space exists in the array, either on the left, the right,
-        // the middle, at both ends, or the entire array is empty. Regardless,
-        // there are two cases, shift some elements to the left or to the right.
-        //
-        let mut r_prime = self.physical_add(index);
-        if len > 0 && index < len {
-            // need to make space for the new element
-            if self.head == 0 || r_prime < self.head {
-                // Slide all elements in S,sub of rank greater than or equal to
-                // r’ and less than (|S,sub| — r’) mod l to the right by one
-                let src = unsafe { self.buffer.add(r_prime) };
-                let dst = unsafe { self.buffer.add(r_prime + 1) };
-                let count = self.count - index;
-                unsafe { std::ptr::copy(src, dst, count) }
-            } else {
-                // Slide all elements in S,sub of rank less than r’ and greater
-                // than or equal to h,sub to the left by one
-                let src = unsafe { self.buffer.add(self.head) };
-                let count = r_prime - self.head;
-                self.head = self.physical_sub(1);
-                let dst = unsafe { self.buffer.add(self.head) };
-                unsafe { std::ptr::copy(src, dst, count) }
-                r_prime -= 1;
+    }
+
+    /// Returns the number of elements for each distinct key produced by the
+    /// given function.
+    ///
+    /// # Time complexity
+    ///
+    /// O(n)
+    #[cfg(feature = "std")]
+    pub fn histogram<K, F>(&self, mut key: F) -> HashMap<K, usize>
+    where
+        K: Hash + Eq,
+        F: FnMut(&T) -> K,
+    {
+        let mut counts: HashMap<K, usize> = HashMap::new();
+        for deque in self.index.iter() {
+            let (first, second) = deque.as_slices();
+            for value in first.iter().chain(second.iter()) {
+                *counts.entry(key(value)).or_insert(0) += 1;
             }
         }
-        unsafe { std::ptr::write(self.buffer.add(r_prime), value) }
-        self.count += 1;
+        counts
     }
 
-    /// Removes and returns the element at position `index` within the array,
-    /// shifting some elements to the left or to the right.
-    pub fn remove(&mut self, index: usize) -> T {
-        let len = self.count;
-        if index >= len {
-            panic!("removal index (is {index}) should be < len (is {len})");
+    /// Consumes the vector and returns an iterator that yields the elements
+    /// in owned arrays of `N` elements. The elements at the end that do not
+    /// fill an array are available from `ArrayChunks::remainder()`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `N` is zero.
+    pub fn into_chunks_exact<const N: usize>(self) -> ArrayChunks<T, N, A> {
+        if N == 0 {
+            panic!("chunk size must be greater than zero");
         }
-        let r_prime = self.physical_add(index);
-        let ret = unsafe { std::ptr::read(self.buffer.add(r_prime)) };
-        if index < (len - 1) {
-            // need to slide elements to fill the new gap
-            if self.head == 0 || r_prime < self.head {
-                // Slide all elements in S,sub of rank r'+1 to h,sub + |S,sub| to
-                // the left by one
-                let src = unsafe { self.buffer.add(r_prime + 1) };
-                let dst = unsafe { self.buffer.add(r_prime) };
-                let count = self.count - index - 1;
-                unsafe { std::ptr::copy(src, dst, count) }
-            } else {
-                // Slide all elements in S,sub of rank greater than or equal to
-                // h,sub and less than r' to the right by one
-                let src = unsafe { self.buffer.add(self.head) };
-                let count = r_prime - self.head;
-                self.head = self.physical_add(1);
-                let dst = unsafe { self.buffer.add(self.head) };
-                unsafe { std::ptr::copy(src, dst, count) }
-            }
+        ArrayChunks {
+            iter: self.into_iter(),
         }
-        self.count -= 1;
-        ret
     }
 
-    /// Provides a reference to the element at the given index.
-    pub fn get(&self, index: usize) -> Option<&T> {
-        if index < self.count {
-            let idx = self.physical_add(index);
-            unsafe { Some(&*self.buffer.add(idx)) }
-        } else {
-            None
-        }
+    /// Returns the minimum and maximum elements, or `None` if the vector is
+    /// empty. As with `Iterator::min()` and `Iterator::max()`, the first of
+    /// several equally minimum elements and the last of several equally
+    /// maximum elements are returned.
+    ///
+    /// Elements are compared in pairs, requiring roughly 1.5 comparisons per
+    /// element rather than 2.
+    ///
+    /// # Time complexity
+    ///
+    /// O(n)
+    pub fn min_max(&self) -> Option<(&T, &T)>
+    where
+        T: Ord,
+    {
+        let mut iter = self.iter();
+        let first = iter.next()?;
+        let (mut min, mut max) = (first, first);
+        while let Some(a) = iter.next() {
+            let (small, large) = match iter.next() {
+                Some(b) if b < a => (b, a),
+                Some(b) => (a, b),
+                None => (a, a),
+            };
+            if small < min {
+                min = small;
+            }
+            if large >= max {
+                max = large;
+            }
+        }
+        Some((min, max))
+    }
+
+    /// Returns the first element with the minimum value of the given key
+    /// function, or `None` if the vector is empty.
+    ///
+    /// # Time complexity
+    ///
+    /// O(n)
+    pub fn min_by_key<K: Ord, F: FnMut(&T) -> K>(&self, mut f: F) -> Option<&T> {
+        self.iter().min_by_key(|v| f(v))
+    }
+
+    /// Returns the last element with the maximum value of the given key
+    /// function, or `None` if the vector is empty.
+    ///
+    /// # Time complexity
+    ///
+    /// O(n)
+    pub fn max_by_key<K: Ord, F: FnMut(&T) -> K>(&self, mut f: F) -> Option<&T> {
+        self.iter().max_by_key(|v| f(v))
+    }
+
+    /// Returns a minimal sequence of edit operations that transforms this
+    /// vector into `other`, based on their longest common subsequence.
+    ///
+    /// The operations are applied in order with a cursor that starts at the
+    /// beginning of the vector: `Keep` advances the cursor past an element,
+    /// `Remove` removes the element at the cursor, and `Insert` inserts an
+    /// element at the cursor and then advances past it. The index of each
+    /// `Insert` and `Remove` is the position of the cursor at that time.
+    ///
+    /// # Time complexity
+    ///
+    /// O(n * m) where m is the length of the other vector, using O(n + m)
+    /// space.
+    pub fn diff(&self, other: &Vector<T, A>) -> Vec<EditOp<T>>
+    where
+        T: Clone + PartialEq,
+    {
+        let mut steps: Vec<DiffStep> = Vec::new();
+        diff_ranges(self, 0..self.count, other, 0..other.count, &mut steps);
+        let mut cursor = 0;
+        let mut edits: Vec<EditOp<T>> = Vec::with_capacity(steps.len());
+        for step in steps {
+            match step {
+                DiffStep::Keep => {
+                    edits.push(EditOp::Keep);
+                    cursor += 1;
+                }
+                DiffStep::Remove => edits.push(EditOp::Remove(cursor)),
+                DiffStep::Insert(index) => {
+                    edits.push(EditOp::Insert(cursor, other[index].clone()));
+                    cursor += 1;
+                }
+            }
+        }
+        edits
+    }
+
+    /// Applies an edit script, such as one produced by `diff()`, to this
+    /// vector. The script is validated before any changes are made, leaving
+    /// the vector unmodified if it is inconsistent with the contents.
+    ///
+    /// # Time complexity
+    ///
+    /// O(e * √N) where e is the number of edit operations.
+    pub fn apply_edits(&mut self, edits: &[EditOp<T>]) -> Result<(), EditError>
+    where
+        T: Clone,
+    {
+        let mut cursor = 0;
+        let mut len = self.count;
+        for (op, edit) in edits.iter().enumerate() {
+            match edit {
+                EditOp::Keep => {
+                    if cursor >= len {
+                        return Err(EditError::OutOfBounds { op });
+                    }
+                    cursor += 1;
+                }
+                EditOp::Insert(index, _) => {
+                    if *index != cursor {
+                        return Err(EditError::IndexMismatch {
+                            op,
+                            expected: cursor,
+                            found: *index,
+                        });
+                    }
+                    cursor += 1;
+                    len += 1;
+                }
+                EditOp::Remove(index) => {
+                    if *index != cursor {
+                        return Err(EditError::IndexMismatch {
+                            op,
+                            expected: cursor,
+                            found: *index,
+                        });
+                    }
+                    if cursor >= len {
+                        return Err(EditError::OutOfBounds { op });
+                    }
+                    len -= 1;
+                }
+            }
+        }
+        for edit in edits {
+            match edit {
+                EditOp::Keep => (),
+                EditOp::Insert(index, value) => self.insert(*index, value.clone()),
+                EditOp::Remove(index) => {
+                    self.remove(*index);
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Copies `out.len()` elements beginning at the logical index `start`
+    /// into the given slice, one contiguous run at a time.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the range of elements extends beyond the end of the vector.
+    ///
+    /// # Time complexity
+    ///
+    /// O(m) where m is the length of the slice.
+    pub fn copy_to_slice(&self, start: usize, out: &mut [T])
+    where
+        T: Copy,
+    {
+        let end = start.saturating_add(out.len());
+        if end > self.count {
+            panic!(
+                "range {start}..{end} out of bounds for length {}",
+                self.count
+            );
+        }
+        let mut copied = 0;
+        while copied < out.len() {
+            let index = start + copied;
+            let offset = index & self.k_mask;
+            let (first, second) = self.index[index >> self.k].as_slices();
+            let run = if offset < first.len() {
+                &first[offset..]
+            } else {
+                &second[offset - first.len()..]
+            };
+            let n = run.len().min(out.len() - copied);
+            unsafe {
+                core::ptr::copy_nonoverlapping(run.as_ptr(), out.as_mut_ptr().add(copied), n);
+            }
+            copied += n;
+        }
+    }
+
+    /// Retrieve a reference to the element at the given index, which may be
+    /// any type that converts into a `usize`, such as a newtype.
+    ///
+    /// # Time complexity
+    ///
+    /// Constant time.
+    pub fn get_by<I: Into<usize>>(&self, index: I) -> Option<&T> {
+        self.get(index.into())
+    }
+
+    /// Returns a mutable reference to the element at the given index, which
+    /// may be any type that converts into a `usize`, such as a newtype.
+    ///
+    /// # Time complexity
+    ///
+    /// Constant time.
+    pub fn get_mut_by<I: Into<usize>>(&mut self, index: I) -> Option<&mut T> {
+        self.get_mut(index.into())
+    }
+
+    /// Returns a new vector containing clones of the elements in reverse
+    /// order, leaving this vector unchanged.
+    ///
+    /// # Time complexity
+    ///
+    /// O(n)
+    pub fn reversed(&self) -> Vector<T, A>
+    where
+        T: Clone,
+    {
+        let mut result: Vector<T, A> = Vector::with_capacity_in(self.count, self.alloc.clone());
+        let mut target = 0;
+        for block in self.index.iter().rev() {
+            let (first, second) = block.as_slices();
+            for value in second.iter().rev().chain(first.iter().rev()) {
+                result.index[target >> result.k].push_back(value.clone());
+                result.count += 1;
+                target += 1;
+            }
+        }
+        result
+    }
+
+    /// Returns a fingerprint of the length and the elements of the vector, in
+    /// order, for detecting changes to its contents. The fingerprint does not
+    /// depend on the layout of the blocks, hence equal vectors share the same
+    /// fingerprint.
+    ///
+    /// The elements are hashed with FNV-1a, whose output is fixed, such that
+    /// the fingerprint is stable across builds and releases of Rust for as
+    /// long as the `Hash` implementation of the element type is unchanged.
+    /// Note that integers, including the length, are hashed in the native byte
+    /// order and width of the target platform.
+    ///
+    /// # Time complexity
+    ///
+    /// O(n)
+    pub fn fingerprint(&self) -> u64
+    where
+        T: Hash,
+    {
+        let mut hasher = Fnv1aHasher::default();
+        self.hash(&mut hasher);
+        hasher.finish()
+    }
+
+    /// Removes the elements in the given range from the vector and returns an
+    /// iterator over the removed elements. The range is removed even if the
+    /// iterator is dropped before it has been fully consumed.
+    ///
+    /// The range is split off into blocks of its own and the elements that
+    /// follow are shifted onto the preceding blocks, as with `split_off()` and
+    /// `append()`. The vector is compressed, if at all, once the iterator is
+    /// dropped.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the start of the range is greater than the end, or if the end
+    /// is greater than the length of the vector.
+    ///
+    /// # Time complexity
+    ///
+    /// O(m + n) in the worst case for the m elements removed, with each of the
+    /// n elements that follow the range moved at most twice.
+    pub fn drain<R: RangeBounds<usize>>(&mut self, range: R) -> Drain<'_, T, A> {
+        let len = self.count;
+        let start = match range.start_bound() {
+            Bound::Included(&start) => start,
+            Bound::Excluded(&start) => start + 1,
+            Bound::Unbounded => 0,
+        };
+        let end = match range.end_bound() {
+            Bound::Included(&end) => end + 1,
+            Bound::Excluded(&end) => end,
+            Bound::Unbounded => len,
+        };
+        if start > end {
+            panic!("drain start (is {start}) should be <= end (is {end})");
+        }
+        if end > len {
+            panic!("drain end (is {end}) should be <= len (is {len})");
+        }
+        let mut tail = self.split_off(end);
+        let removed = self.split_off(start);
+        self.append(&mut tail);
+        Drain {
+            iter: removed.into_iter(),
+            vector: self,
+        }
+    }
+
+    /// Returns the sum of the products of corresponding elements of this
+    /// vector and `other`. Products are summed in groups of `DOT_GROUP` before
+    /// being added to the total, which reduces the accumulated rounding error
+    /// for floating point types.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the vectors have different lengths.
+    ///
+    /// # Time complexity
+    ///
+    /// O(n)
+    pub fn dot(&self, other: &Vector<T, A>) -> T
+    where
+        T: Copy + Add<Output = T> + Mul<Output = T> + Default,
+    {
+        if self.count != other.count {
+            panic!(
+                "vector lengths (are {} and {}) should be equal",
+                self.count, other.count
+            );
+        }
+        let mut total = T::default();
+        let mut partial = T::default();
+        for (offset, (a, b)) in self
+            .runs()
+            .flatten()
+            .zip(other.runs().flatten())
+            .enumerate()
+        {
+            partial = partial + *a * *b;
+            if (offset + 1) % DOT_GROUP == 0 {
+                total = total + partial;
+                partial = T::default();
+            }
+        }
+        total + partial
+    }
+
+    /// Returns the contiguous runs of elements of each block, in order.
+    fn runs(&self) -> impl Iterator<Item = &[T]> {
+        self.blocks().flat_map(|(first, second)| [first, second])
+    }
+
+    /// Returns an iterator over the blocks that hold elements, yielding for
+    /// each block the pair of slices that contain its elements in order, as
+    /// with `CyclicArray::as_slices()`. The second slice is empty unless the
+    /// elements of the block wrap around the end of its buffer.
+    ///
+    /// # Time complexity
+    ///
+    /// O(√N) over the entire iteration.
+    pub fn blocks(&self) -> impl Iterator<Item = (&[T], &[T])> {
+        self.index
+            .iter()
+            .filter(|block| !block.is_empty())
+            .map(|block| block.as_slices())
+    }
+
+    /// Retains only the elements for which the predicate returns `true`,
+    /// preserving their relative order. Surviving elements are moved forward
+    /// in a single pass and the remainder are dropped from the tail.
+    ///
+    /// # Time complexity
+    ///
+    /// O(n)
+    pub fn retain<F: FnMut(&T) -> bool>(&mut self, mut f: F) {
+        let mut kept = 0;
+        for index in 0..self.count {
+            if f(&self[index]) {
+                if kept != index {
+                    self.swap(kept, index);
+                }
+                kept += 1;
+            }
+        }
+        self.truncate(kept);
+    }
+
+    /// Returns a new vector containing clones of the elements at each of the
+    /// given offsets, in the order given. Offsets may be repeated.
+    ///
+    /// # Panics
+    ///
+    /// Panics if any offset is out of bounds.
+    ///
+    /// # Time complexity
+    ///
+    /// O(m) where m is the number of offsets.
+    pub fn gather(&self, indices: &[usize]) -> Vector<T, A>
+    where
+        T: Clone,
+    {
+        let mut gathered: Vector<T, A> =
+            Vector::with_capacity_in(indices.len(), self.alloc.clone());
+        for &index in indices {
+            gathered.push(self[index].clone());
+        }
+        gathered
+    }
+
+    /// Writes each of the values into the slot at the corresponding offset,
+    /// dropping the previous element. When an offset is repeated, the later
+    /// value is the one that remains.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the number of offsets and values differ, or if any offset is
+    /// out of bounds, in which case the vector is left unmodified.
+    ///
+    /// # Time complexity
+    ///
+    /// O(m) where m is the number of offsets.
+    pub fn scatter(&mut self, indices: &[usize], values: Vec<T>) {
+        if indices.len() != values.len() {
+            panic!(
+                "indices length (is {}) should be == values length (is {})",
+                indices.len(),
+                values.len()
+            );
+        }
+        let len = self.count;
+        if let Some(index) = indices.iter().find(|&&index| index >= len) {
+            panic!("scatter index (is {index}) should be < len (is {len})");
+        }
+        for (&index, value) in indices.iter().zip(values) {
+            self[index] = value;
+        }
+    }
+
+    /// Returns an iterator of owned windows of `size` elements, the start of
+    /// each window being `step` elements after the previous one. Iteration
+    /// stops when there are not enough elements to fill a window.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `size` or `step` is zero.
+    ///
+    /// # Time complexity
+    ///
+    /// O(size) for each window.
+    pub fn cloned_windows(&self, size: usize, step: usize) -> impl Iterator<Item = Vec<T>>
+    where
+        T: Clone,
+    {
+        if size == 0 {
+            panic!("window size must be greater than zero");
+        }
+        if step == 0 {
+            panic!("step must be greater than zero");
+        }
+        let windows = if self.count >= size {
+            (self.count - size) / step + 1
+        } else {
+            0
+        };
+        (0..windows).map(move |window| {
+            let start = window * step;
+            (start..start + size)
+                .map(|index| self[index].clone())
+                .collect()
+        })
+    }
+
+    /// Returns the arithmetic mean of the elements, or `None` if the vector is
+    /// empty.
+    ///
+    /// # Time complexity
+    ///
+    /// O(n)
+    pub fn mean(&self) -> Option<f64>
+    where
+        T: Copy + Into<f64>,
+    {
+        self.welford().map(|(mean, _)| mean)
+    }
+
+    /// Returns the population variance of the elements, that is the mean of
+    /// the squared differences from the mean, or `None` if the vector is
+    /// empty.
+    ///
+    /// # Time complexity
+    ///
+    /// O(n)
+    pub fn variance(&self) -> Option<f64>
+    where
+        T: Copy + Into<f64>,
+    {
+        self.welford().map(|(_, m2)| m2 / self.count as f64)
+    }
+
+    /// Computes the mean and the sum of squared differences from the mean in a
+    /// single pass using Welford's algorithm, or `None` if the vector is empty.
+    fn welford(&self) -> Option<(f64, f64)>
+    where
+        T: Copy + Into<f64>,
+    {
+        if self.count == 0 {
+            return None;
+        }
+        let mut mean = 0.0;
+        let mut m2 = 0.0;
+        for (n, value) in self.runs().flatten().enumerate() {
+            let x: f64 = (*value).into();
+            let delta = x - mean;
+            mean += delta / (n + 1) as f64;
+            m2 += delta * (x - mean);
+        }
+        Some((mean, m2))
+    }
+
+    /// Removes every element that is equal to an earlier element, keeping only
+    /// the first occurrence of each value in its original order.
+    ///
+    /// # Time complexity
+    ///
+    /// O(n)
+    #[cfg(feature = "std")]
+    pub fn unique(&mut self)
+    where
+        T: Hash + Eq,
+    {
+        let keep: Vec<bool> = {
+            let mut seen: HashSet<&T> = HashSet::new();
+            self.iter().map(|value| seen.insert(value)).collect()
+        };
+        let mut keep = keep.into_iter();
+        self.retain(|_| keep.next().unwrap_or(true));
+    }
+
+    /// Inserts the value into a sorted vector such that it remains sorted,
+    /// placing it after any equal elements, and returns the offset at which it
+    /// was inserted.
+    ///
+    /// # Time complexity
+    ///
+    /// O(log n) to find the position and O(√N) to insert.
+    pub fn insert_sorted(&mut self, value: T) -> usize
+    where
+        T: Ord,
+    {
+        let mut low = 0;
+        let mut high = self.count;
+        while low < high {
+            let mid = low + (high - low) / 2;
+            if self[mid] <= value {
+                low = mid + 1;
+            } else {
+                high = mid;
+            }
+        }
+        self.insert(low, value);
+        low
+    }
+
+    /// Consumes the vector and moves each element into a vector for the key
+    /// derived from it, preserving the relative order of the elements within
+    /// each group.
+    ///
+    /// # Time complexity
+    ///
+    /// O(n)
+    #[cfg(feature = "std")]
+    pub fn group_by_key<K, F>(self, mut key: F) -> HashMap<K, Vector<T, A>>
+    where
+        K: Hash + Eq,
+        F: FnMut(&T) -> K,
+    {
+        let mut groups: HashMap<K, Vector<T, A>> = HashMap::new();
+        let alloc = self.alloc.clone();
+        for value in self {
+            groups
+                .entry(key(&value))
+                .or_insert_with(|| Vector::new_in(alloc.clone()))
+                .push(value);
+        }
+        groups
+    }
+
+    /// Returns an iterator of views of the runs of elements separated by
+    /// elements equal to `sep`. As with `slice::split()`, leading, trailing,
+    /// and consecutive separators result in empty views.
+    ///
+    /// # Time complexity
+    ///
+    /// O(n) over the entire iteration.
+    pub fn split_on(&self, sep: &T) -> impl Iterator<Item = VectorSlice<'_, T, A>>
+    where
+        T: PartialEq,
+    {
+        let mut start = Some(0);
+        core::iter::from_fn(move || {
+            let from = start?;
+            let end = (from..self.count)
+                .find(|&index| self[index] == *sep)
+                .unwrap_or(self.count);
+            start = if end < self.count {
+                Some(end + 1)
+            } else {
+                None
+            };
+            Some(VectorSlice {
+                vector: self,
+                start: from,
+                end,
+            })
+        })
+    }
+
+    /// Splits the vector into two at the given offset, returning a vector
+    /// containing the elements `[at, len)` and leaving this vector with the
+    /// elements `[0, at)`. Whole blocks are moved into the new vector, with
+    /// only the elements of the block at the boundary needing to be shifted
+    /// between the blocks that follow.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `at > len`.
+    ///
+    /// # Time complexity
+    ///
+    /// O(√N) if `at` falls on a block boundary, otherwise O(N) in the worst
+    /// case.
+    pub fn split_off(&mut self, at: usize) -> Vector<T, A> {
+        let len = self.count;
+        if at > len {
+            panic!("`at` split index (is {at}) should be <= len (is {len})");
+        }
+        let mut other: Vector<T, A> = Vector::new_in(self.alloc.clone());
+        if at == len {
+            return other;
+        }
+        other.set_exponent(self.k);
+        let sub = at >> self.k;
+        let r_prime = at & self.k_mask;
+        if r_prime == 0 {
+            other.index = self.index.split_off(sub);
+            let moved = other.index.len();
+            self.give_stats_to(&mut other, moved);
+        } else {
+            let following = self.index.split_off(sub + 1);
+            self.give_stats_to(&mut other, following.len());
+            let boundary = &mut self.index[sub];
+            let mut first = other.alloc_block(other.l);
+            while boundary.len() > r_prime {
+                first.push_front(boundary.pop_back().unwrap());
+            }
+            other.index.push(first);
+            // shift the leading elements of each block onto the previous one
+            for mut block in following {
+                let previous = other.index.last_mut().unwrap();
+                for _ in 0..r_prime {
+                    match block.pop_front() {
+                        Some(value) => previous.push_back(value),
+                        None => break,
+                    }
+                }
+                if block.is_empty() {
+                    other.dealloc_block(block);
+                } else {
+                    other.index.push(block);
+                }
+            }
+        }
+        other.count = len - at;
+        self.count = at;
+        other
+    }
+
+    /// Moves all of the elements of `other` onto the end of this vector,
+    /// leaving `other` empty. When both vectors have the same block size and
+    /// the result does not require larger blocks, the blocks of `other` are
+    /// moved whole, with only the elements needed to fill the last block of
+    /// this vector being shifted between blocks.
+    ///
+    /// # Time complexity
+    ///
+    /// O(√N) if this vector ends on a block boundary, otherwise O(m) for the m
+    /// elements of `other`.
+    pub fn append(&mut self, other: &mut Vector<T, A>) {
+        if other.count == 0 {
+            return;
+        }
+        if self.count == 0 && self.index.is_empty() {
+            core::mem::swap(self, other);
+            // the counters of this vector were swapped into other
+            self.take_stats_from(other);
+        } else if self.k == other.k && self.count + other.count <= self.upper_limit {
+            self.take_stats_from(other);
+            let added = other.count;
+            other.count = 0;
+            self.adopt_blocks(core::mem::take(&mut other.index));
+            self.count += added;
+        } else {
+            self.reserve(other.count);
+            for value in other.take_elements() {
+                self.push(value);
+            }
+        }
+    }
+
+    /// Moves the given blocks, which must have the same capacity as those of
+    /// this vector and be full except for the last, onto the end of this
+    /// vector. If the last block of this vector is partially filled, leading
+    /// elements of each block are shifted onto the block before it. The count
+    /// is left for the caller to update.
+    fn adopt_blocks(&mut self, blocks: Vec<CyclicArray<T, A>>) {
+        self.trim_empty_blocks();
+        let filled = self.count & self.k_mask;
+        let needed = if filled == 0 { 0 } else { self.l - filled };
+        for mut block in blocks {
+            if needed > 0 {
+                let previous = self.index.last_mut().unwrap();
+                for _ in 0..needed {
+                    match block.pop_front() {
+                        Some(value) => previous.push_back(value),
+                        None => break,
+                    }
+                }
+            }
+            if block.is_empty() {
+                self.dealloc_block(block);
+            } else {
+                self.index.push(block);
+            }
+        }
+    }
+
+    /// Reverses the order of the elements in place.
+    ///
+    /// # Time complexity
+    ///
+    /// O(n)
+    pub fn reverse(&mut self) {
+        self.reverse_range(0, self.count);
+    }
+
+    /// Reverses the order of the elements in the range `start..end`.
+    fn reverse_range(&mut self, mut start: usize, mut end: usize) {
+        while start + 1 < end {
+            end -= 1;
+            self.swap(start, end);
+            start += 1;
+        }
+    }
+
+    /// Rotates the elements in place such that the element at `mid` becomes
+    /// the first element, as with `slice::rotate_left()`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `mid` is greater than the length of the vector.
+    ///
+    /// # Time complexity
+    ///
+    /// O(n)
+    pub fn rotate_left(&mut self, mid: usize) {
+        let len = self.count;
+        if mid > len {
+            panic!("rotation (is {mid}) should be <= len (is {len})");
+        }
+        self.reverse_range(0, mid);
+        self.reverse_range(mid, len);
+        self.reverse_range(0, len);
+    }
+
+    /// Rotates the elements in place such that the last `k` elements move to
+    /// the front, as with `slice::rotate_right()`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `k` is greater than the length of the vector.
+    ///
+    /// # Time complexity
+    ///
+    /// O(n)
+    pub fn rotate_right(&mut self, k: usize) {
+        let len = self.count;
+        if k > len {
+            panic!("rotation (is {k}) should be <= len (is {len})");
+        }
+        self.rotate_left(len - k);
+    }
+
+    // Returns an iterator over the vector.
+    //
+    // The iterator yields all items from start to end.
+    pub fn iter(&self) -> VectorIter<'_, T, A> {
+        VectorIter {
+            array: self,
+            index: 0,
+            end: self.count,
+        }
+    }
+
+    /// Returns an iterator over the elements within the given range of
+    /// offsets.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the start of the range is greater than the end, or if the end
+    /// is greater than the length of the vector.
+    pub fn range<R: RangeBounds<usize>>(&self, range: R) -> VectorIter<'_, T, A> {
+        let (start, end) = self.bounds_of(range);
+        VectorIter {
+            array: self,
+            index: start,
+            end,
+        }
+    }
+
+    /// Returns an iterator that yields mutable references to the elements
+    /// within the given range of offsets.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the start of the range is greater than the end, or if the end
+    /// is greater than the length of the vector.
+    pub fn range_mut<R: RangeBounds<usize>>(&mut self, range: R) -> VectorIterMut<'_, T, A> {
+        let (start, end) = self.bounds_of(range);
+        let mut iter = VectorIterMut {
+            remaining: end - start,
+            blocks: self.index[start >> self.k..].iter_mut(),
+            first: Default::default(),
+            second: Default::default(),
+        };
+        if start < end {
+            // position the iterator within the block holding the start
+            let offset = start & self.k_mask;
+            let (first, second) = iter.blocks.next().unwrap().as_mut_slices();
+            if offset < first.len() {
+                iter.first = first[offset..].iter_mut();
+                iter.second = second.iter_mut();
+            } else {
+                iter.second = second[offset - first.len()..].iter_mut();
+            }
+        }
+        iter
+    }
+
+    /// Resolve the given range into start and end offsets, panicking if the
+    /// range is not valid for this vector.
+    fn bounds_of<R: RangeBounds<usize>>(&self, range: R) -> (usize, usize) {
+        let len = self.count;
+        let start = match range.start_bound() {
+            Bound::Included(&start) => start,
+            Bound::Excluded(&start) => start + 1,
+            Bound::Unbounded => 0,
+        };
+        let end = match range.end_bound() {
+            Bound::Included(&end) => end + 1,
+            Bound::Excluded(&end) => end,
+            Bound::Unbounded => len,
+        };
+        if start > end {
+            panic!("range start (is {start}) should be <= end (is {end})");
+        }
+        if end > len {
+            panic!("range end (is {end}) should be <= len (is {len})");
+        }
+        (start, end)
+    }
+
+    /// Returns an iterator that yields mutable references to the elements in
+    /// order, walking each block in turn.
+    pub fn iter_mut(&mut self) -> VectorIterMut<'_, T, A> {
+        VectorIterMut {
+            remaining: self.count,
+            blocks: self.index.iter_mut(),
+            first: Default::default(),
+            second: Default::default(),
+        }
+    }
+
+    /// Returns an iterator that yields each element along with the offset of
+    /// the block that holds it and the physical slot it occupies within the
+    /// buffer of that block. This is intended for diagnostic purposes only.
+    pub fn iter_physical(&self) -> impl Iterator<Item = (usize, usize, &T)> {
+        self.index.iter().enumerate().flat_map(|(block, deque)| {
+            (0..deque.len()).map(move |offset| (block, deque.physical_add(offset), &deque[offset]))
+        })
+    }
+
+    /// Returns an iterator over each pair of consecutive elements, yielding
+    /// `n - 1` pairs in logical order.
+    ///
+    /// The elements are read directly from the contiguous runs within each
+    /// block rather than being looked up by index.
+    pub fn pairs(&self) -> impl Iterator<Item = (&T, &T)> {
+        let mut elements = self.index.iter().flat_map(|deque| {
+            let (first, second) = deque.as_slices();
+            first.iter().chain(second.iter())
+        });
+        let mut previous = elements.next();
+        core::iter::from_fn(move || {
+            let a = previous?;
+            let b = elements.next()?;
+            previous = Some(b);
+            Some((a, b))
+        })
+    }
+
+    /// Return the number of elements in the vector.
+    ///
+    /// # Time complexity
+    ///
+    /// Constant time.
+    pub fn len(&self) -> usize {
+        self.count
+    }
+
+    /// Returns the total number of elements the vector can hold without
+    /// reallocating.
+    ///
+    /// # Time complexity
+    ///
+    /// Constant time.
+    pub fn capacity(&self) -> usize {
+        (1 << self.k) * self.index.len()
+    }
+
+    /// Returns true if the array has a length of 0.
+    ///
+    /// # Time complexity
+    ///
+    /// Constant time.
+    pub fn is_empty(&self) -> bool {
+        self.count == 0
+    }
+
+    /// Releases the spare capacity of the dope vector, which may be retained
+    /// after the number of blocks has decreased significantly.
+    ///
+    /// # Time complexity
+    ///
+    /// O(√N)
+    pub fn shrink_dope(&mut self) {
+        self.index.shrink_to_fit();
+    }
+
+    /// Releases unused capacity by compressing the vector to the smallest
+    /// block size that can hold the elements, no smaller than 4 (or the size
+    /// given to `with_tier_exponent()`), and deallocating any empty blocks at
+    /// the end.
+    ///
+    /// # Time complexity
+    ///
+    /// O(n) if the vector is compressed, otherwise O(√N)
+    pub fn shrink_to_fit(&mut self) {
+        self.trim_empty_blocks();
+        while self.k > self.min_k && self.count <= self.upper_limit / 4 {
+            self.compress();
+            self.trim_empty_blocks();
+        }
+    }
+
+    /// Reduces the capacity left unused after loading elements into the vector
+    /// by deallocating empty blocks at the end and, while the last block is
+    /// less than half full and the elements fit within smaller blocks,
+    /// compressing the vector. Has no effect if the capacity is already well
+    /// fitted to the length.
+    ///
+    /// # Time complexity
+    ///
+    /// O(n) if the vector is compressed, otherwise O(√N)
+    pub fn optimize_after_load(&mut self) {
+        self.trim_empty_blocks();
+        while self.k > self.min_k
+            && self.count <= self.upper_limit / 4
+            && self.index.last().is_some_and(|b| b.len() < self.l / 2)
+        {
+            self.compress();
+            self.trim_empty_blocks();
+        }
+    }
+
+    /// Deallocates any empty blocks at the end of the dope vector.
+    fn trim_empty_blocks(&mut self) {
+        while self.index.last().is_some_and(|b| b.is_empty()) {
+            let block = self.index.pop().unwrap();
+            self.dealloc_block(block);
+        }
+    }
+
+    /// Clears the vector, removing all values and deallocating all blocks,
+    /// including any spare blocks, such that the vector returns to the state
+    /// of a newly constructed vector with zero capacity. A vector created by
+    /// `with_tier_exponent()` keeps its block size.
+    ///
+    /// # Time complexity
+    ///
+    /// O(n) if elements are droppable, otherwise O(√N)
+    pub fn clear(&mut self) {
+        self.record_deallocs(self.index.len());
+        self.index.clear();
+        self.count = 0;
+        self.set_exponent(self.min_k);
+    }
+
+    /// Clears the vector, removing all values but retaining the blocks and
+    /// the block size, such that refilling the vector to its former capacity
+    /// does not allocate. Use `clear()` to release the blocks instead.
+    ///
+    /// The emptied blocks remain as spare blocks at the end of the vector,
+    /// which are filled before any more are allocated. Should the vector later
+    /// be compressed because it holds too few elements for the block size,
+    /// the spare blocks are released at that time rather than being split.
+    ///
+    /// # Time complexity
+    ///
+    /// O(n) if elements are droppable, otherwise O(√N)
+    pub fn clear_keep_capacity(&mut self) {
+        for block in self.index.iter_mut() {
+            block.clear();
+        }
+        self.count = 0;
+    }
+}
+
+impl<A: Allocator + Clone> Vector<u32, A> {
+    /// Returns the index of the first element equal to `target`, or `None` if
+    /// there is no such element.
+    ///
+    /// On `x86_64` the contiguous runs of each block are compared four elements
+    /// at a time using SSE2 instructions, while other targets fall back to the
+    /// same search as `find_copy()`.
+    ///
+    /// # Time complexity
+    ///
+    /// O(n)
+    pub fn find_u32(&self, target: u32) -> Option<usize> {
+        self.find_in_runs(|run| find_u32_in_slice(run, target))
+    }
+}
+
+impl<T> Default for Vector<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: Clone, A: Allocator + Clone> Clone for Vector<T, A> {
+    fn clone(&self) -> Self {
+        let mut cloned = Self::new_in(self.alloc.clone());
+        cloned.set_exponent(self.k);
+        cloned.min_k = self.min_k;
+        cloned.index = self.index.clone();
+        cloned.count = self.count;
+        cloned.record_allocs(cloned.index.len(), cloned.l);
+        cloned
+    }
+}
+
+impl<T, A: Allocator + Clone> fmt::Display for Vector<T, A> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "Vector(k: {}, count: {}, dope: {})",
+            self.k,
+            self.count,
+            self.index.len(),
+        )
+    }
+}
+
+impl<T: PartialEq, A: Allocator + Clone> PartialEq for Vector<T, A> {
+    fn eq(&self, other: &Self) -> bool {
+        self.count == other.count && self.iter().zip(other.iter()).all(|(a, b)| a == b)
+    }
+}
+
+impl<T: Eq, A: Allocator + Clone> Eq for Vector<T, A> {}
+
+impl<T: PartialOrd, A: Allocator + Clone> PartialOrd for Vector<T, A> {
+    fn partial_cmp(&self, other: &Self) -> Option<core::cmp::Ordering> {
+        self.iter().partial_cmp(other.iter())
+    }
+}
+
+impl<T: Ord, A: Allocator + Clone> Ord for Vector<T, A> {
+    fn cmp(&self, other: &Self) -> core::cmp::Ordering {
+        self.iter().cmp(other.iter())
+    }
+}
+
+impl<T: Hash, A: Allocator + Clone> Hash for Vector<T, A> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.count.hash(state);
+        for value in self.iter() {
+            value.hash(state);
+        }
+    }
+}
+
+impl<T: fmt::Debug, A: Allocator + Clone> fmt::Debug for Vector<T, A> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list().entries(self.iter()).finish()
+    }
+}
+
+impl<T, A: Allocator + Clone> Index<usize> for Vector<T, A> {
+    type Output = T;
+
+    fn index(&self, index: usize) -> &Self::Output {
+        let Some(item) = self.get(index) else {
+            panic!("index out of bounds: {}", index);
+        };
+        item
+    }
+}
+
+impl<T, A: Allocator + Clone> IndexMut<usize> for Vector<T, A> {
+    fn index_mut(&mut self, index: usize) -> &mut Self::Output {
+        let Some(item) = self.get_mut(index) else {
+            panic!("index out of bounds: {}", index);
+        };
+        item
+    }
+}
+
+impl<A> FromIterator<A> for Vector<A> {
+    fn from_iter<T: IntoIterator<Item = A>>(iter: T) -> Self {
+        let iter = iter.into_iter();
+        let mut arr: Vector<A> = Vector::with_capacity(iter.size_hint().0);
+        for value in iter {
+            arr.push(value)
+        }
+        arr
+    }
+}
+
+impl<T> From<Vec<T>> for Vector<T> {
+    fn from(vec: Vec<T>) -> Self {
+        let mut vector: Vector<T> = Vector::with_capacity(vec.len());
+        for value in vec {
+            vector.push(value);
+        }
+        vector
+    }
+}
+
+impl<T, const N: usize> From<[T; N]> for Vector<T> {
+    fn from(array: [T; N]) -> Self {
+        let mut vector: Vector<T> = Vector::with_capacity(N);
+        for value in array {
+            vector.push(value);
+        }
+        vector
+    }
+}
+
+impl<T: Clone> From<&[T]> for Vector<T> {
+    fn from(slice: &[T]) -> Self {
+        let mut vector: Vector<T> = Vector::with_capacity(slice.len());
+        for value in slice {
+            vector.push(value.clone());
+        }
+        vector
+    }
+}
+
+impl<T> From<Vector<T>> for Vec<T> {
+    /// Converts the vector into a `Vec`, taking ownership of the buffer of the
+    /// first block without copying when it holds every element starting at
+    /// the beginning of the buffer, otherwise moving each element.
+    fn from(mut vector: Vector<T>) -> Self {
+        // every block is either handed over or deallocated
+        vector.record_deallocs(vector.index.len());
+        let fits = vector
+            .index
+            .first()
+            .is_some_and(|block| block.head == 0 && block.count == vector.count);
+        if fits && core::mem::size_of::<T>() > 0 {
+            // any blocks after the first are empty and are simply dropped
+            let mut index = core::mem::take(&mut vector.index);
+            vector.count = 0;
+            let block = core::mem::ManuallyDrop::new(index.swap_remove(0));
+            // the buffer was allocated by the global allocator with the same
+            // layout as that of a Vec with this capacity
+            return unsafe { Vec::from_raw_parts(block.buffer, block.count, block.capacity) };
+        }
+        vector.into_iter().collect()
+    }
+}
+
+impl<T, A: Allocator + Clone> Extend<T> for Vector<T, A> {
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        let iter = iter.into_iter();
+        self.reserve(iter.size_hint().0);
+        for value in iter {
+            self.push(value)
+        }
+    }
+}
+
+impl<'a, T: Copy + 'a, A: Allocator + Clone> Extend<&'a T> for Vector<T, A> {
+    fn extend<I: IntoIterator<Item = &'a T>>(&mut self, iter: I) {
+        self.extend(iter.into_iter().copied())
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<T: serde::Serialize, A: Allocator + Clone> serde::Serialize for Vector<T, A> {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_seq(self.iter())
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de, T: serde::Deserialize<'de>> serde::Deserialize<'de> for Vector<T> {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct VectorVisitor<T>(core::marker::PhantomData<T>);
+
+        impl<'de, T: serde::Deserialize<'de>> serde::de::Visitor<'de> for VectorVisitor<T> {
+            type Value = Vector<T>;
+
+            fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+                formatter.write_str("a sequence")
+            }
+
+            fn visit_seq<A: serde::de::SeqAccess<'de>>(
+                self,
+                mut seq: A,
+            ) -> Result<Self::Value, A::Error> {
+                // do not trust the size hint enough to allocate without limit
+                let capacity = seq.size_hint().unwrap_or(0).min(65_536);
+                let mut vector: Vector<T> = Vector::with_capacity(capacity);
+                while let Some(value) = seq.next_element()? {
+                    vector.push(value);
+                }
+                Ok(vector)
+            }
+        }
+
+        deserializer.deserialize_seq(VectorVisitor(core::marker::PhantomData))
+    }
+}
+
+#[cfg(feature = "arbitrary")]
+impl<'a, T: arbitrary::Arbitrary<'a>> arbitrary::Arbitrary<'a> for Vector<T> {
+    fn arbitrary(u: &mut arbitrary::Unstructured<'a>) -> arbitrary::Result<Self> {
+        let len = u.arbitrary_len::<T>()?;
+        let mut arr: Vector<T> = Vector::with_capacity(len);
+        for _ in 0..len {
+            arr.push(T::arbitrary(u)?);
+        }
+        Ok(arr)
+    }
+
+    fn arbitrary_take_rest(u: arbitrary::Unstructured<'a>) -> arbitrary::Result<Self> {
+        u.arbitrary_take_rest_iter()?.collect()
+    }
+
+    fn size_hint(_depth: usize) -> (usize, Option<usize>) {
+        (0, None)
+    }
+}
+
+/// Hasher implementing the 64-bit FNV-1a algorithm, used where the hash of a
+/// value must not change between releases, as `DefaultHasher` might.
+struct Fnv1aHasher(u64);
+
+impl Default for Fnv1aHasher {
+    fn default() -> Self {
+        Self(0xcbf2_9ce4_8422_2325)
+    }
+}
+
+impl Hasher for Fnv1aHasher {
+    fn finish(&self) -> u64 {
+        self.0
+    }
+
+    fn write(&mut self, bytes: &[u8]) {
+        for byte in bytes {
+            self.0 ^= u64::from(*byte);
+            self.0 = self.0.wrapping_mul(0x0100_0000_01b3);
+        }
+    }
+}
+
+/// Number of elements compared at once by `find_in_slice()`.
+const FIND_LANES: usize = 16;
+
+/// Returns the offset of the first element in `slice` equal to `target`.
+fn find_in_slice<T: Copy + PartialEq>(slice: &[T], target: T) -> Option<usize> {
+    let chunks = slice.chunks_exact(FIND_LANES);
+    let remainder = chunks.remainder();
+    for (number, chunk) in chunks.enumerate() {
+        // compare every lane without short-circuiting so that the loop can be
+        // vectorized, then locate the match only when one was found
+        let found = chunk
+            .iter()
+            .fold(false, |acc, value| acc | (*value == target));
+        if found {
+            let offset = chunk.iter().position(|value| *value == target)?;
+            return Some(number * FIND_LANES + offset);
+        }
+    }
+    let offset = remainder.iter().position(|value| *value == target)?;
+    Some(slice.len() - remainder.len() + offset)
+}
+
+/// Returns the offset of the first element in `slice` equal to `target`,
+/// comparing four elements at a time with SSE2, which every `x86_64` processor
+/// supports.
+#[cfg(target_arch = "x86_64")]
+fn find_u32_in_slice(slice: &[u32], target: u32) -> Option<usize> {
+    use core::arch::x86_64::{
+        __m128i, _mm_cmpeq_epi32, _mm_loadu_si128, _mm_movemask_epi8, _mm_set1_epi32,
+    };
+    let chunks = slice.chunks_exact(4);
+    let remainder = chunks.remainder();
+    unsafe {
+        let needle = _mm_set1_epi32(target as i32);
+        for (number, chunk) in chunks.enumerate() {
+            let values = _mm_loadu_si128(chunk.as_ptr().cast::<__m128i>());
+            // one bit per byte, hence four bits for each matching element
+            let mask = _mm_movemask_epi8(_mm_cmpeq_epi32(values, needle));
+            if mask != 0 {
+                return Some(number * 4 + mask.trailing_zeros() as usize / 4);
+            }
+        }
+    }
+    let offset = find_in_slice(remainder, target)?;
+    Some(slice.len() - remainder.len() + offset)
+}
+
+/// Returns the offset of the first element in `slice` equal to `target`.
+#[cfg(not(target_arch = "x86_64"))]
+fn find_u32_in_slice(slice: &[u32], target: u32) -> Option<usize> {
+    find_in_slice(slice, target)
+}
+
+/// Panics if a vector of length `len` cannot be viewed with `cols` columns.
+fn check_matrix_cols(len: usize, cols: usize) {
+    if cols == 0 || !len.is_multiple_of(cols) {
+        panic!("length (is {len}) should be a multiple of cols (is {cols})");
+    }
+}
+
+/// Read-only view of a tiered vector as a row-major matrix.
+pub struct MatrixView<'a, T, A: Allocator = Global> {
+    vector: &'a Vector<T, A>,
+    cols: usize,
+}
+
+impl<'a, T, A: Allocator + Clone> MatrixView<'a, T, A> {
+    /// Returns the number of rows in the matrix.
+    pub fn rows(&self) -> usize {
+        self.vector.len() / self.cols
+    }
+
+    /// Returns the number of columns in the matrix.
+    pub fn cols(&self) -> usize {
+        self.cols
+    }
+
+    /// Retrieve a reference to the element at the given row and column.
+    ///
+    /// # Time complexity
+    ///
+    /// Constant time.
+    pub fn get(&self, row: usize, col: usize) -> Option<&'a T> {
+        if col < self.cols {
+            self.vector.get(row * self.cols + col)
+        } else {
+            None
+        }
+    }
+
+    /// Returns an iterator over the elements of the given row, which will be
+    /// empty if the row is out of bounds.
+    pub fn row(&self, row: usize) -> impl Iterator<Item = &'a T> + use<'a, T, A> {
+        let vector = self.vector;
+        let start = if row < self.rows() {
+            row * self.cols
+        } else {
+            vector.len()
+        };
+        let end = (start + self.cols).min(vector.len());
+        (start..end).filter_map(move |index| vector.get(index))
+    }
+}
+
+/// Mutable view of a tiered vector as a row-major matrix.
+pub struct MatrixViewMut<'a, T, A: Allocator = Global> {
+    vector: &'a mut Vector<T, A>,
+    cols: usize,
+}
+
+impl<T, A: Allocator + Clone> MatrixViewMut<'_, T, A> {
+    /// Returns the number of rows in the matrix.
+    pub fn rows(&self) -> usize {
+        self.vector.len() / self.cols
+    }
+
+    /// Returns the number of columns in the matrix.
+    pub fn cols(&self) -> usize {
+        self.cols
+    }
+
+    /// Retrieve a reference to the element at the given row and column.
+    ///
+    /// # Time complexity
+    ///
+    /// Constant time.
+    pub fn get(&self, row: usize, col: usize) -> Option<&T> {
+        if col < self.cols {
+            self.vector.get(row * self.cols + col)
+        } else {
+            None
+        }
+    }
+
+    /// Returns a mutable reference to the element at the given row and column.
+    ///
+    /// # Time complexity
+    ///
+    /// Constant time.
+    pub fn get_mut(&mut self, row: usize, col: usize) -> Option<&mut T> {
+        if col < self.cols {
+            self.vector.get_mut(row * self.cols + col)
+        } else {
+            None
+        }
+    }
+}
+
+/// Handle to a background thread that is dropping the contents of a vector.
+#[cfg(feature = "thread")]
+pub struct DropHandle {
+    handle: std::thread::JoinHandle<()>,
+}
+
+#[cfg(feature = "thread")]
+impl DropHandle {
+    /// Waits for all of the elements to be dropped.
+    ///
+    /// # Panics
+    ///
+    /// Panics if dropping any of the elements caused a panic.
+    pub fn join(self) {
+        if let Err(err) = self.handle.join() {
+            std::panic::resume_unwind(err);
+        }
+    }
+
+    /// Returns true if all of the elements have been dropped.
+    pub fn is_finished(&self) -> bool {
+        self.handle.is_finished()
+    }
+}
+
+/// Error returned by `Vector::reshape_to_exponent()`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ReshapeError {
+    /// blocks must hold at least 4 elements
+    ExponentTooSmall,
+    /// blocks would be too large to address
+    ExponentTooLarge,
+    /// elements would require more blocks than permitted for the block size
+    TooManyElements,
+}
+
+impl fmt::Display for ReshapeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ReshapeError::ExponentTooSmall => write!(f, "block exponent must be at least 2"),
+            ReshapeError::ExponentTooLarge => write!(f, "block exponent is too large"),
+            ReshapeError::TooManyElements => {
+                write!(f, "elements do not fit within blocks of the given size")
+            }
+        }
+    }
+}
+
+impl core::error::Error for ReshapeError {}
+
+/// Handle to an element of a vector, obtained from `Vector::entry()`.
+pub struct Entry<'a, T> {
+    value: &'a mut T,
+}
+
+impl<'a, T> Entry<'a, T> {
+    /// Returns a reference to the element.
+    pub fn get(&self) -> &T {
+        self.value
+    }
+
+    /// Returns a mutable reference to the element.
+    pub fn get_mut(&mut self) -> &mut T {
+        self.value
+    }
+
+    /// Converts the entry into a mutable reference with the lifetime of the
+    /// vector.
+    pub fn into_mut(self) -> &'a mut T {
+        self.value
+    }
+
+    /// Replaces the element with the given value, returning the old value.
+    pub fn replace(self, value: T) -> T {
+        core::mem::replace(self.value, value)
+    }
+
+    /// Modifies the element in place using the given function.
+    pub fn and_modify<F: FnOnce(&mut T)>(self, f: F) -> Self {
+        f(self.value);
+        self
+    }
+}
+
+/// An iterator that moves out of a tiered vector in arrays of `N` elements,
+/// obtained from `Vector::into_chunks_exact()`.
+pub struct ArrayChunks<T, const N: usize, A: Allocator = Global> {
+    iter: VectorIntoIter<T, A>,
+}
+
+impl<T, const N: usize, A: Allocator + Clone> ArrayChunks<T, N, A> {
+    /// Consumes the iterator and returns the elements at the end of the vector
+    /// that do not fill an array. Any arrays that have not yet been yielded
+    /// are dropped.
+    pub fn remainder(mut self) -> Vec<T> {
+        let complete = self.iter.count - self.iter.count % N;
+        for _ in 0..complete {
+            self.iter.next();
+        }
+        self.iter.collect()
+    }
+}
+
+impl<T, const N: usize, A: Allocator + Clone> Iterator for ArrayChunks<T, N, A> {
+    type Item = [T; N];
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.iter.count < N {
+            return None;
+        }
+        let iter = &mut self.iter;
+        Some(core::array::from_fn(|_| iter.next().unwrap()))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let chunks = self.iter.count / N;
+        (chunks, Some(chunks))
+    }
+}
+
+/// An iterator over the elements removed by `Vector::drain()`.
+pub struct Drain<'a, T, A: Allocator + Clone = Global> {
+    iter: VectorIntoIter<T, A>,
+    /// vector from which the elements were removed
+    vector: &'a mut Vector<T, A>,
+}
+
+impl<T, A: Allocator + Clone> Drop for Drain<'_, T, A> {
+    fn drop(&mut self) {
+        // compress only once, rather than as each element was removed
+        self.vector.compress_to_fit();
+    }
+}
+
+impl<T, A: Allocator + Clone> Iterator for Drain<'_, T, A> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.iter.next()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.iter.size_hint()
+    }
+}
+
+impl<T, A: Allocator + Clone> DoubleEndedIterator for Drain<'_, T, A> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.iter.next_back()
+    }
+}
+
+/// Immutable view of a contiguous range of the elements of a vector.
+pub struct VectorSlice<'a, T, A: Allocator = Global> {
+    vector: &'a Vector<T, A>,
+    /// offset of the first element within the vector
+    start: usize,
+    /// offset just past the last element within the vector
+    end: usize,
+}
+
+impl<'a, T, A: Allocator + Clone> VectorSlice<'a, T, A> {
+    /// Retrieve a reference to the element at the given offset within the view.
+    ///
+    /// # Time complexity
+    ///
+    /// Constant time.
+    pub fn get(&self, index: usize) -> Option<&'a T> {
+        if index < self.len() {
+            self.vector.get(self.start + index)
+        } else {
+            None
+        }
+    }
+
+    /// Returns an iterator over the elements of the view.
+    pub fn iter(&self) -> impl Iterator<Item = &'a T> + use<'a, T, A> {
+        let vector = self.vector;
+        (self.start..self.end).map(move |index| &vector[index])
+    }
+
+    /// Returns the range of offsets within the vector covered by the view.
+    pub fn range(&self) -> core::ops::Range<usize> {
+        self.start..self.end
+    }
+
+    /// Return the number of elements in the view.
+    pub fn len(&self) -> usize {
+        self.end - self.start
+    }
+
+    /// Returns true if the view has a length of 0.
+    pub fn is_empty(&self) -> bool {
+        self.start == self.end
+    }
+}
+
+/// Provides access to a vector within `Vector::batch()` such that inserting and
+/// removing elements never causes the vector to expand or compress.
+pub struct BatchGuard<'a, T, A: Allocator = Global> {
+    vector: &'a mut Vector<T, A>,
+}
+
+impl<T, A: Allocator + Clone> BatchGuard<'_, T, A> {
+    /// Inserts an element at position `index` within the vector, shifting some
+    /// elements to the right as needed.
+    pub fn insert(&mut self, index: usize, value: T) {
+        self.vector.insert_with(index, value, false);
+    }
+
+    /// Appends an element to the back of the vector.
+    pub fn push(&mut self, value: T) {
+        self.vector.insert_with(self.vector.count, value, false);
+    }
+
+    /// Removes an element from position `index` within the vector, shifting
+    /// some elements to the left as needed to close the gap.
+    pub fn remove(&mut self, index: usize) -> T {
+        self.vector.remove_with(index, false)
+    }
+
+    /// Removes the last element from the vector and returns it, or `None` if
+    /// the vector is empty.
+    pub fn pop(&mut self) -> Option<T> {
+        if self.vector.count > 0 {
+            Some(self.vector.remove_with(self.vector.count - 1, false))
+        } else {
+            None
+        }
+    }
+
+    /// Retrieve a reference to the element at the given offset.
+    pub fn get(&self, index: usize) -> Option<&T> {
+        self.vector.get(index)
+    }
+
+    /// Returns a mutable reference to an element.
+    pub fn get_mut(&mut self, index: usize) -> Option<&mut T> {
+        self.vector.get_mut(index)
+    }
+
+    /// Return the number of elements in the vector.
+    pub fn len(&self) -> usize {
+        self.vector.len()
+    }
+
+    /// Returns true if the vector has a length of 0.
+    pub fn is_empty(&self) -> bool {
+        self.vector.is_empty()
+    }
+}
+
+/// Number of products summed together by `Vector::dot()` before being added to
+/// the total.
+const DOT_GROUP: usize = 64;
+
+/// Edit operation produced by `Vector::diff()`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum EditOp<T> {
+    /// retain the element at the cursor
+    Keep,
+    /// insert the value at the given index
+    Insert(usize, T),
+    /// remove the element at the given index
+    Remove(usize),
+}
+
+/// Error returned by `Vector::apply_edits()` for an inconsistent script.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum EditError {
+    /// operation at the given position refers past the end of the vector
+    OutOfBounds { op: usize },
+    /// operation at the given position has an index other than the cursor
+    IndexMismatch {
+        op: usize,
+        expected: usize,
+        found: usize,
+    },
+}
+
+impl fmt::Display for EditError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            EditError::OutOfBounds { op } => {
+                write!(f, "edit {op} refers past the end of the vector")
+            }
+            EditError::IndexMismatch {
+                op,
+                expected,
+                found,
+            } => write!(f, "edit {op} has index {found} but expected {expected}"),
+        }
+    }
+}
+
+impl core::error::Error for EditError {}
+
+/// Step in the alignment of two vectors computed by `diff_ranges()`.
+enum DiffStep {
+    /// element is common to both
+    Keep,
+    /// element appears only in the first vector
+    Remove,
+    /// element at the given index appears only in the second vector
+    Insert(usize),
+}
+
+/// Computes the lengths of the longest common subsequences of the elements
+/// of `a` in `a_range` and every prefix of the elements of `b` in `b_range`,
+/// or every suffix if `reverse` is true, using two rows of the usual table.
+fn lcs_lengths<T: PartialEq, A: Allocator + Clone>(
+    a: &Vector<T, A>,
+    a_range: core::ops::Range<usize>,
+    b: &Vector<T, A>,
+    b_range: core::ops::Range<usize>,
+    reverse: bool,
+) -> Vec<usize> {
+    let m = b_range.len();
+    let mut previous: Vec<usize> = vec![0; m + 1];
+    let mut current: Vec<usize> = vec![0; m + 1];
+    for row in 0..a_range.len() {
+        let i = if reverse {
+            a_range.end - 1 - row
+        } else {
+            a_range.start + row
+        };
+        for j in 1..=m {
+            let b_index = if reverse {
+                b_range.end - j
+            } else {
+                b_range.start + j - 1
+            };
+            current[j] = if a[i] == b[b_index] {
+                previous[j - 1] + 1
+            } else {
+                previous[j].max(current[j - 1])
+            };
+        }
+        core::mem::swap(&mut previous, &mut current);
+    }
+    previous
+}
+
+/// Aligns the elements of `a` in `a_range` with the elements of `b` in
+/// `b_range` using Hirschberg's algorithm, appending the steps to `steps`.
+fn diff_ranges<T: PartialEq, A: Allocator + Clone>(
+    a: &Vector<T, A>,
+    a_range: core::ops::Range<usize>,
+    b: &Vector<T, A>,
+    b_range: core::ops::Range<usize>,
+    steps: &mut Vec<DiffStep>,
+) {
+    if a_range.is_empty() {
+        steps.extend(b_range.map(DiffStep::Insert));
+    } else if b_range.is_empty() {
+        steps.extend(a_range.map(|_| DiffStep::Remove));
+    } else if a_range.len() == 1 {
+        let value = &a[a_range.start];
+        if let Some(found) = b_range.clone().find(|&j| b[j] == *value) {
+            steps.extend((b_range.start..found).map(DiffStep::Insert));
+            steps.push(DiffStep::Keep);
+            steps.extend((found + 1..b_range.end).map(DiffStep::Insert));
+        } else {
+            steps.push(DiffStep::Remove);
+            steps.extend(b_range.map(DiffStep::Insert));
+        }
+    } else {
+        let mid = a_range.start + a_range.len() / 2;
+        let m = b_range.len();
+        let front = lcs_lengths(a, a_range.start..mid, b, b_range.clone(), false);
+        let back = lcs_lengths(a, mid..a_range.end, b, b_range.clone(), true);
+        let split = (0..=m).max_by_key(|&k| front[k] + back[m - k]).unwrap_or(0);
+        let b_mid = b_range.start + split;
+        diff_ranges(a, a_range.start..mid, b, b_range.start..b_mid, steps);
+        diff_ranges(a, mid..a_range.end, b, b_mid..b_range.end, steps);
+    }
+}
+
+/// Immutable array iterator.
+pub struct VectorIter<'a, T, A: Allocator = Global> {
+    array: &'a Vector<T, A>,
+    index: usize,
+    end: usize,
+}
+
+impl<'a, T, A: Allocator + Clone> Iterator for VectorIter<'a, T, A> {
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.index < self.end {
+            let value = self.array.get(self.index);
+            self.index += 1;
+            value
+        } else {
+            None
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let remaining = self.end - self.index;
+        (remaining, Some(remaining))
+    }
+}
+
+impl<T, A: Allocator + Clone> ExactSizeIterator for VectorIter<'_, T, A> {}
+
+impl<T, A: Allocator + Clone> core::iter::FusedIterator for VectorIter<'_, T, A> {}
+
+/// Mutable array iterator.
+pub struct VectorIterMut<'a, T, A: Allocator = Global> {
+    /// number of elements not yet yielded
+    remaining: usize,
+    /// blocks that have not yet been visited
+    blocks: core::slice::IterMut<'a, CyclicArray<T, A>>,
+    /// leading run of the current block
+    first: core::slice::IterMut<'a, T>,
+    /// trailing run of the current block
+    second: core::slice::IterMut<'a, T>,
+}
+
+impl<'a, T, A: Allocator + Clone> Iterator for VectorIterMut<'a, T, A> {
+    type Item = &'a mut T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.remaining == 0 {
+            return None;
+        }
+        loop {
+            if let Some(value) = self.first.next().or_else(|| self.second.next()) {
+                self.remaining -= 1;
+                return Some(value);
+            }
+            let (first, second) = self.blocks.next()?.as_mut_slices();
+            self.first = first.iter_mut();
+            self.second = second.iter_mut();
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.remaining, Some(self.remaining))
+    }
+}
+
+impl<T, A: Allocator + Clone> ExactSizeIterator for VectorIterMut<'_, T, A> {}
+
+impl<T, A: Allocator + Clone> core::iter::FusedIterator for VectorIterMut<'_, T, A> {}
+
+impl<T, A: Allocator + Clone> IntoIterator for Vector<T, A> {
+    type Item = T;
+    type IntoIter = VectorIntoIter<Self::Item, A>;
+
+    fn into_iter(mut self) -> Self::IntoIter {
+        let index = core::mem::take(&mut self.index);
+        VectorIntoIter {
+            count: self.count,
+            front: 0,
+            index,
+        }
+    }
+}
+
+/// An iterator that moves out of a tiered vector.
+pub struct VectorIntoIter<T, A: Allocator = Global> {
+    /// number of remaining elements
+    count: usize,
+    /// offset of the first circular deque that has not been consumed
+    front: usize,
+    /// index of circular deques
+    index: Vec<CyclicArray<T, A>>,
+}
+
+impl<T, A: Allocator + Clone> Iterator for VectorIntoIter<T, A> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.count > 0 {
+            let ret = self.index[self.front].pop_front();
+            self.count -= 1;
+            if self.index[self.front].is_empty() {
+                // release the consumed deque without shifting the index
+                let alloc = self.index[self.front].alloc.clone();
+                self.index[self.front] = CyclicArray::new_in(0, alloc);
+                self.front += 1;
+            }
+            ret
+        } else {
+            None
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.count, Some(self.count))
+    }
+}
+
+impl<T, A: Allocator + Clone> ExactSizeIterator for VectorIntoIter<T, A> {}
+
+impl<T, A: Allocator + Clone> core::iter::FusedIterator for VectorIntoIter<T, A> {}
+
+impl<T, A: Allocator + Clone> DoubleEndedIterator for VectorIntoIter<T, A> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        if self.count > 0 {
+            // skip over any unused blocks at the end
+            while self.index.last().is_some_and(|b| b.is_empty()) {
+                self.index.pop();
+            }
+            let last = self.index.last_mut()?;
+            let ret = last.pop_back();
+            self.count -= 1;
+            if last.is_empty() {
+                self.index.pop();
+            }
+            ret
+        } else {
+            None
+        }
+    }
+}
+
+/// Fixed capacity ring of the most recently pushed elements, backed by a tiered
+/// vector, in which the oldest element is evicted to make room for a new one.
+pub struct RingVector<T> {
+    /// elements from oldest to newest
+    vector: Vector<T>,
+    /// maximum number of elements retained
+    capacity: usize,
+}
+
+impl<T> RingVector<T> {
+    /// Return an empty ring that retains at most `capacity` elements.
+    pub fn with_capacity(capacity: usize) -> Self {
+        Self {
+            vector: Vector::with_capacity(capacity),
+            capacity,
+        }
+    }
+
+    /// Appends an element to the ring, returning the oldest element if it was
+    /// evicted to make room for the new one.
+    ///
+    /// # Time complexity
+    ///
+    /// O(√N) in the worst case.
+    pub fn push(&mut self, value: T) -> Option<T> {
+        if self.capacity == 0 {
+            return Some(value);
+        }
+        let evicted = if self.vector.len() == self.capacity {
+            Some(self.vector.remove(0))
+        } else {
+            None
+        };
+        self.vector.push(value);
+        evicted
+    }
+
+    /// Returns an iterator over the most recent `k` elements, from oldest to
+    /// newest. Fewer elements are yielded if the ring holds fewer than `k`.
+    pub fn recent(&self, k: usize) -> impl Iterator<Item = &T> {
+        self.vector.iter().skip(self.vector.len().saturating_sub(k))
+    }
+
+    /// Return the number of elements in the ring.
+    pub fn len(&self) -> usize {
+        self.vector.len()
+    }
+
+    /// Returns true if the ring has a length of 0.
+    pub fn is_empty(&self) -> bool {
+        self.vector.is_empty()
+    }
+
+    /// Returns the maximum number of elements retained by the ring.
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+}
+
+/// Tiered vector paired with a hash map of its elements, providing constant
+/// time membership tests while retaining the order of the elements.
+///
+/// The map records the number of occurrences of each element, so duplicate
+/// elements are permitted and membership remains accurate as they are removed.
+#[cfg(feature = "std")]
+pub struct IndexedVector<T: Hash + Eq> {
+    /// elements in their logical order
+    vector: Vector<T>,
+    /// number of occurrences of each element
+    counts: HashMap<T, usize>,
+}
+
+#[cfg(feature = "std")]
+impl<T: Hash + Eq + Clone> IndexedVector<T> {
+    /// Return an empty indexed vector with zero capacity.
+    pub fn new() -> Self {
+        Self {
+            vector: Vector::new(),
+            counts: HashMap::new(),
+        }
+    }
+
+    /// Inserts an element at position `index` within the vector, shifting some
+    /// elements to the right as needed.
+    ///
+    /// # Time complexity
+    ///
+    /// O(√N) in the worst case.
+    pub fn insert(&mut self, index: usize, value: T) {
+        let key = value.clone();
+        self.vector.insert(index, value);
+        *self.counts.entry(key).or_insert(0) += 1;
+    }
+
+    /// Appends an element to the back of the vector.
+    ///
+    /// # Time complexity
+    ///
+    /// O(√N) in the worst case.
+    pub fn push(&mut self, value: T) {
+        self.insert(self.vector.len(), value);
+    }
+
+    /// Removes an element from position `index` within the vector, shifting
+    /// some elements to the left as needed to close the gap.
+    ///
+    /// # Time complexity
+    ///
+    /// O(√N) in the worst case.
+    pub fn remove(&mut self, index: usize) -> T {
+        let value = self.vector.remove(index);
+        self.forget(&value);
+        value
+    }
+
+    /// Removes the last element from the vector and returns it, or `None` if
+    /// the vector is empty.
+    ///
+    /// # Time complexity
+    ///
+    /// O(√N) in the worst case.
+    pub fn pop(&mut self) -> Option<T> {
+        let value = self.vector.pop()?;
+        self.forget(&value);
+        Some(value)
+    }
+
+    /// Decrement the occurrences of the given value, dropping the entry from
+    /// the map when none remain.
+    fn forget(&mut self, value: &T) {
+        if let Some(count) = self.counts.get_mut(value) {
+            *count -= 1;
+            if *count == 0 {
+                self.counts.remove(value);
+            }
+        }
+    }
+
+    /// Returns true if the vector contains an element equal to `value`.
+    ///
+    /// # Time complexity
+    ///
+    /// Constant time.
+    pub fn contains(&self, value: &T) -> bool {
+        self.counts.contains_key(value)
+    }
+
+    /// Retrieve a reference to the element at the given offset.
+    ///
+    /// # Time complexity
+    ///
+    /// Constant time.
+    pub fn get(&self, index: usize) -> Option<&T> {
+        self.vector.get(index)
+    }
+
+    /// Returns an iterator over the elements in order.
+    pub fn iter(&self) -> VectorIter<'_, T> {
+        self.vector.iter()
+    }
+
+    /// Return the number of elements in the vector.
+    ///
+    /// # Time complexity
+    ///
+    /// Constant time.
+    pub fn len(&self) -> usize {
+        self.vector.len()
+    }
+
+    /// Returns true if the vector has a length of 0.
+    ///
+    /// # Time complexity
+    ///
+    /// Constant time.
+    pub fn is_empty(&self) -> bool {
+        self.vector.is_empty()
+    }
+}
+
+#[cfg(feature = "std")]
+impl<T: Hash + Eq + Clone> Default for IndexedVector<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Tiered vector that is replaced wholesale by writers while being read
+/// concurrently, without locking, by any number of readers.
+///
+/// Each version of the vector is immutable once published; readers obtain a
+/// snapshot that remains valid for as long as they hold it.
+#[cfg(feature = "arc-swap")]
+pub struct PublishedVector<T> {
+    current: arc_swap::ArcSwap<Vector<T>>,
+}
+
+#[cfg(feature = "arc-swap")]
+impl<T> PublishedVector<T> {
+    /// Return a published vector whose initial version is the given vector.
+    pub fn new(vector: Vector<T>) -> Self {
+        Self {
+            current: arc_swap::ArcSwap::from_pointee(vector),
+        }
+    }
+
+    /// Returns a snapshot of the current version of the vector.
+    pub fn load(&self) -> std::sync::Arc<Vector<T>> {
+        self.current.load_full()
+    }
+
+    /// Atomically replaces the current version with the given vector.
+    pub fn store(&self, vector: Vector<T>) {
+        self.current.store(std::sync::Arc::new(vector));
+    }
+}
+
+#[cfg(feature = "arc-swap")]
+impl<T> Default for PublishedVector<T> {
+    fn default() -> Self {
+        Self::new(Vector::new())
+    }
+}
+
+/// Parallel iterator over references to the elements of a vector, in which
+/// each block is processed as an independent piece of work.
+#[cfg(feature = "rayon")]
+pub struct ParIter<'a, T> {
+    blocks: &'a [CyclicArray<T>],
+}
+
+#[cfg(feature = "rayon")]
+impl<'a, T: Sync + 'a> rayon::iter::ParallelIterator for ParIter<'a, T> {
+    type Item = &'a T;
+
+    fn drive_unindexed<C>(self, consumer: C) -> C::Result
+    where
+        C: rayon::iter::plumbing::UnindexedConsumer<Self::Item>,
+    {
+        use rayon::iter::IntoParallelIterator;
+        self.blocks
+            .into_par_iter()
+            .flat_map_iter(|block| {
+                let (first, second) = block.as_slices();
+                first.iter().chain(second)
+            })
+            .drive_unindexed(consumer)
+    }
+}
+
+#[cfg(feature = "rayon")]
+impl<'a, T: Sync + 'a> rayon::iter::IntoParallelIterator for &'a Vector<T> {
+    type Iter = ParIter<'a, T>;
+    type Item = &'a T;
+
+    fn into_par_iter(self) -> Self::Iter {
+        ParIter {
+            blocks: &self.index,
+        }
+    }
+}
+
+/// Parallel iterator over mutable references to the elements of a vector, in
+/// which each block is processed as an independent piece of work.
+#[cfg(feature = "rayon")]
+pub struct ParIterMut<'a, T> {
+    blocks: &'a mut [CyclicArray<T>],
+}
+
+#[cfg(feature = "rayon")]
+impl<'a, T: Send + 'a> rayon::iter::ParallelIterator for ParIterMut<'a, T> {
+    type Item = &'a mut T;
+
+    fn drive_unindexed<C>(self, consumer: C) -> C::Result
+    where
+        C: rayon::iter::plumbing::UnindexedConsumer<Self::Item>,
+    {
+        use rayon::iter::IntoParallelIterator;
+        self.blocks
+            .into_par_iter()
+            .flat_map_iter(|block| {
+                let (first, second) = block.as_mut_slices();
+                first.iter_mut().chain(second)
+            })
+            .drive_unindexed(consumer)
+    }
+}
+
+#[cfg(feature = "rayon")]
+impl<'a, T: Send + 'a> rayon::iter::IntoParallelIterator for &'a mut Vector<T> {
+    type Iter = ParIterMut<'a, T>;
+    type Item = &'a mut T;
+
+    fn into_par_iter(self) -> Self::Iter {
+        ParIterMut {
+            blocks: &mut self.index,
+        }
+    }
+}
+
+/// Basic circular buffer, or what Goodrich and Kloss call a circular deque.
+///
+/// This implementation allows push and pop from both ends of the buffer and
+/// supports insert and remove from arbitrary offsets.
+///
+/// Unlike the `VecDeque` in the standard library, this array has a fixed size
+/// and will panic if a push is performed while the array is already full.
+pub struct CyclicArray<T, A: Allocator = Global> {
+    /// allocated buffer of size `capacity`
+    buffer: *mut T,
+    /// number of slots allocated in the buffer
+    capacity: usize,
+    /// offset of the first entry
+    head: usize,
+    /// number of elements
+    count: usize,
+    /// allocator of the buffer
+    alloc: A,
+}
+
+// The cyclic array owns its elements, the same as `Vec`.
+unsafe impl<T: Send, A: Allocator + Send> Send for CyclicArray<T, A> {}
+unsafe impl<T: Sync, A: Allocator + Sync> Sync for CyclicArray<T, A> {}
+
+impl<T> CyclicArray<T> {
+    /// Construct a new cyclic array with the given capacity.
+    pub fn new(capacity: usize) -> Self {
+        Self::new_in(capacity, Global)
+    }
+}
+
+impl<T, A: Allocator> CyclicArray<T, A> {
+    /// Construct a new cyclic array with the given capacity, allocating the
+    /// buffer using the given allocator.
+    pub fn new_in(capacity: usize, alloc: A) -> Self {
+        Self {
+            buffer: Self::allocate(capacity, &alloc),
+            capacity,
+            head: 0,
+            count: 0,
+            alloc,
+        }
+    }
+
+    /// Allocate a buffer for `capacity` elements using the given allocator.
+    fn allocate(capacity: usize, alloc: &A) -> *mut T {
+        let layout = Layout::array::<T>(capacity).expect("unexpected overflow");
+        if layout.size() == 0 {
+            // nothing is allocated for a zero capacity array
+            return NonNull::dangling().as_ptr();
+        }
+        match do_alloc(alloc, layout) {
+            Some(ptr) => ptr.as_ptr().cast::<T>(),
+            None => handle_alloc_error(layout),
+        }
+    }
+
+    /// Free the buffer for this cyclic array without dropping the elements.
+    fn dealloc(&mut self) {
+        // nothing was allocated for a zero capacity array
+        let layout = Layout::array::<T>(self.capacity).expect("unexpected overflow");
+        if layout.size() > 0 {
+            unsafe {
+                let ptr = NonNull::new_unchecked(self.buffer.cast::<u8>());
+                self.alloc.deallocate(ptr, layout);
+            }
+        }
+    }
+
+    /// Take the elements from the two other cyclic arrays into a new cyclic
+    /// array with the combined capacity.
+    pub fn combine(a: CyclicArray<T, A>, b: CyclicArray<T, A>) -> Self
+    where
+        A: Clone,
+    {
+        let capacity = a.capacity + b.capacity;
+        let mut this: CyclicArray<T, A> = CyclicArray::new_in(capacity, a.alloc.clone());
+        let mut this_pos = 0;
+        let their_a = core::mem::ManuallyDrop::new(a);
+        let their_b = core::mem::ManuallyDrop::new(b);
+        for mut other in [their_a, their_b] {
+            if other.head + other.count > other.capacity {
+                // data wraps around, copy as two blocks
+                let src = unsafe { other.buffer.add(other.head) };
+                let dst = unsafe { this.buffer.add(this_pos) };
+                let count_1 = other.capacity - other.head;
+                unsafe { core::ptr::copy(src, dst, count_1) }
+                this_pos += count_1;
+                let dst = unsafe { this.buffer.add(this_pos) };
+                let count_2 = other.count - count_1;
+                unsafe { core::ptr::copy(other.buffer, dst, count_2) }
+                this_pos += count_2;
+            } else {
+                // data is contiguous, copy as one block
+                let src = unsafe { other.buffer.add(other.head) };
+                let dst = unsafe { this.buffer.add(this_pos) };
+                unsafe { core::ptr::copy(src, dst, other.count) }
+                this_pos += other.count;
+            }
+            other.dealloc();
+            this.count += other.count;
+        }
+        this
+    }
+
+    /// Take the elements from the other cyclic array into a new cyclic array
+    /// with the given capacity.
+    pub fn from(capacity: usize, other: CyclicArray<T, A>) -> Self
+    where
+        A: Clone,
+    {
+        assert!(capacity > other.count, "capacity cannot be less than count");
+        let buffer = Self::allocate(capacity, &other.alloc);
+        let mut them = core::mem::ManuallyDrop::new(other);
+        if them.head + them.count > them.capacity {
+            // data wraps around, copy as two blocks
+            let src = unsafe { them.buffer.add(them.head) };
+            let count_1 = them.capacity - them.head;
+            unsafe { core::ptr::copy(src, buffer, count_1) }
+            let dst = unsafe { buffer.add(count_1) };
+            let count_2 = them.count - count_1;
+            unsafe { core::ptr::copy(them.buffer, dst, count_2) }
+        } else {
+            // data is contiguous, copy as one block
+            let src = unsafe { them.buffer.add(them.head) };
+            unsafe { core::ptr::copy(src, buffer, them.count) }
+        }
+        them.dealloc();
+        Self {
+            buffer,
+            capacity,
+            head: 0,
+            count: them.count,
+            alloc: them.alloc.clone(),
+        }
+    }
+
+    /// Split this cyclic buffer into two equal sized buffers.
+    ///
+    /// The second buffer may be empty if all elements fit within the first
+    /// buffer.
+    pub fn split(self) -> (CyclicArray<T, A>, CyclicArray<T, A>)
+    where
+        A: Clone,
+    {
+        assert!(
+            self.capacity.is_multiple_of(2),
+            "capacity must be an even number"
+        );
+        let half = self.capacity / 2;
+        let mut me = core::mem::ManuallyDrop::new(self);
+        let mut a: CyclicArray<T, A> = CyclicArray::new_in(half, me.alloc.clone());
+        let mut b: CyclicArray<T, A> = CyclicArray::new_in(half, me.alloc.clone());
+        let mut remaining = me.count;
+        for other in [&mut a, &mut b] {
+            let mut other_pos = 0;
+            while remaining > 0 && !other.is_full() {
+                let want_to_copy = if me.head + remaining > me.capacity {
+                    me.capacity - me.head
+                } else {
+                    remaining
+                };
+                let can_fit = other.capacity - other.count;
+                let to_copy = if want_to_copy > can_fit {
+                    can_fit
+                } else {
+                    want_to_copy
+                };
+                let src = unsafe { me.buffer.add(me.head) };
+                let dst = unsafe { other.buffer.add(other_pos) };
+                unsafe { core::ptr::copy(src, dst, to_copy) };
+                other_pos += to_copy;
+                other.count += to_copy;
+                me.head = me.physical_add(to_copy);
+                remaining -= to_copy;
+            }
+        }
+        me.dealloc();
+        (a, b)
+    }
+
+    /// Appends an element to the back of the cyclic array.
+    ///
+    /// # Panic
+    ///
+    /// Panics if the buffer is already full.
+    pub fn push_back(&mut self, value: T) {
+        if self.count == self.capacity {
+            panic!("cyclic array is full")
+        }
+        let off = self.physical_add(self.count);
+        unsafe { core::ptr::write(self.buffer.add(off), value) }
+        self.count += 1;
+    }
+
+    /// Prepends an element to the front of the cyclic array.
+    ///
+    /// # Panic
+    ///
+    /// Panics if the buffer is already full.
+    pub fn push_front(&mut self, value: T) {
+        if self.count == self.capacity {
+            panic!("cyclic array is full")
+        }
+        self.head = self.physical_sub(1);
+        unsafe { core::ptr::write(self.buffer.add(self.head), value) }
+        self.count += 1;
+    }
+
+    /// Removes the last element and returns it, or `None` if the cyclic array
+    /// is empty.
+    pub fn pop_back(&mut self) -> Option<T> {
+        if self.count == 0 {
+            None
+        } else {
+            self.count -= 1;
+            let off = self.physical_add(self.count);
+            unsafe { Some(core::ptr::read(self.buffer.add(off))) }
+        }
+    }
+
+    /// Removes the first element and returns it, or `None` if the cyclic array
+    /// is empty.
+    pub fn pop_front(&mut self) -> Option<T> {
+        if self.count == 0 {
+            None
+        } else {
+            let old_head = self.head;
+            self.head = self.physical_add(1);
+            self.count -= 1;
+            unsafe { Some(core::ptr::read(self.buffer.add(old_head))) }
+        }
+    }
+
+    /// Inserts an element at position `index` within the array, possibly
+    /// shifting some elements to the left or the right as needed.
+    pub fn insert(&mut self, index: usize, value: T) {
+        let len = self.count;
+        if index > len {
+            panic!("insertion index (is {index}) should be <= len (is {len})");
+        }
+        if len == self.capacity {
+            panic!("cyclic array is full")
+        }
+        //
+        // Some free space exists in the array, either on the left, the right,
+        // the middle, at both ends, or the entire array is empty. Regardless,
+        // there are two cases, shift some elements to the left or to the right.
+        //
+        let mut r_prime = self.physical_add(index);
+        if len > 0 && index < len {
+            // need to make space for the new element
+            if self.head == 0 || r_prime < self.head {
+                // Slide all elements in S,sub of rank greater than or equal to
+                // r’ and less than (|S,sub| — r’) mod l to the right by one
+                let src = unsafe { self.buffer.add(r_prime) };
+                let dst = unsafe { self.buffer.add(r_prime + 1) };
+                let count = self.count - index;
+                unsafe { core::ptr::copy(src, dst, count) }
+            } else {
+                // Slide all elements in S,sub of rank less than r’ and greater
+                // than or equal to h,sub to the left by one
+                let src = unsafe { self.buffer.add(self.head) };
+                let count = r_prime - self.head;
+                self.head = self.physical_sub(1);
+                let dst = unsafe { self.buffer.add(self.head) };
+                unsafe { core::ptr::copy(src, dst, count) }
+                r_prime -= 1;
+            }
+        }
+        unsafe { core::ptr::write(self.buffer.add(r_prime), value) }
+        self.count += 1;
+    }
+
+    /// Returns the number of elements that would be moved to make room for an
+    /// insertion at position `index`, were the array to hold `len` elements
+    /// starting at the current head.
+    fn insert_moves(&self, index: usize, len: usize) -> usize {
+        if len > 0 && index < len {
+            let r_prime = self.physical_add(index);
+            if self.head == 0 || r_prime < self.head {
+                len - index
+            } else {
+                r_prime - self.head
+            }
+        } else {
+            0
+        }
+    }
+
+    /// Removes and returns the element at position `index` within the array,
+    /// shifting some elements to the left or to the right.
+    pub fn remove(&mut self, index: usize) -> T {
+        let len = self.count;
+        if index >= len {
+            panic!("removal index (is {index}) should be < len (is {len})");
+        }
+        let r_prime = self.physical_add(index);
+        let ret = unsafe { core::ptr::read(self.buffer.add(r_prime)) };
+        if index < (len - 1) {
+            // need to slide elements to fill the new gap
+            if self.head == 0 || r_prime < self.head {
+                // Slide all elements in S,sub of rank r'+1 to h,sub + |S,sub| to
+                // the left by one
+                let src = unsafe { self.buffer.add(r_prime + 1) };
+                let dst = unsafe { self.buffer.add(r_prime) };
+                let count = self.count - index - 1;
+                unsafe { core::ptr::copy(src, dst, count) }
+            } else {
+                // Slide all elements in S,sub of rank greater than or equal to
+                // h,sub and less than r' to the right by one
+                let src = unsafe { self.buffer.add(self.head) };
+                let count = r_prime - self.head;
+                self.head = self.physical_add(1);
+                let dst = unsafe { self.buffer.add(self.head) };
+                unsafe { core::ptr::copy(src, dst, count) }
+            }
+        }
+        self.count -= 1;
+        ret
+    }
+
+    /// Provides a reference to the element at the given index.
+    pub fn get(&self, index: usize) -> Option<&T> {
+        if index < self.count {
+            let idx = self.physical_add(index);
+            unsafe { Some(&*self.buffer.add(idx)) }
+        } else {
+            None
+        }
+    }
+
+    /// Returns a mutable reference to an element.
+    pub fn get_mut(&mut self, index: usize) -> Option<&mut T> {
+        if index < self.count {
+            let idx = self.physical_add(index);
+            unsafe { (self.buffer.add(idx)).as_mut() }
+        } else {
+            None
+        }
+    }
+
+    /// Returns a pair of slices which contain, in order, the contents of the
+    /// cyclic array. The second slice will be empty unless the elements wrap
+    /// around the end of the buffer.
+    pub fn as_slices(&self) -> (&[T], &[T]) {
+        if self.count == 0 {
+            return (&[], &[]);
+        }
+        let first = self.count.min(self.capacity - self.head);
+        unsafe {
+            (
+                core::slice::from_raw_parts(self.buffer.add(self.head), first),
+                core::slice::from_raw_parts(self.buffer, self.count - first),
+            )
+        }
+    }
+
+    /// Returns a pair of mutable slices which contain, in order, the contents
+    /// of the cyclic array.
+    pub fn as_mut_slices(&mut self) -> (&mut [T], &mut [T]) {
+        if self.count == 0 {
+            return (&mut [], &mut []);
+        }
+        let first = self.count.min(self.capacity - self.head);
+        unsafe {
+            (
+                core::slice::from_raw_parts_mut(self.buffer.add(self.head), first),
+                core::slice::from_raw_parts_mut(self.buffer, self.count - first),
+            )
+        }
+    }
+
+    /// Returns the unoccupied slots following the last element as up to two
+    /// physical runs of the buffer, in logical order. The second run will be
+    /// empty unless the free space wraps around the end of the buffer.
+    fn spare_runs(&mut self) -> [(*mut T, usize); 2] {
+        let spare = self.capacity - self.count;
+        if spare == 0 {
+            return [(self.buffer, 0), (self.buffer, 0)];
+        }
+        let start = self.physical_add(self.count);
+        let first = spare.min(self.capacity - start);
+        let first_ptr = unsafe { self.buffer.add(start) };
+        [(first_ptr, first), (self.buffer, spare - first)]
+    }
+
+    /// Sets the number of elements in the array without dropping or
+    /// initializing anything.
+    ///
+    /// # Safety
+    ///
+    /// The slots between the old and the new length must have been
+    /// initialized, and `count` must not exceed the capacity.
+    unsafe fn set_len(&mut self, count: usize) {
+        debug_assert!(count <= self.capacity);
+        self.count = count;
+    }
+
+    /// Clears the cyclic array, removing and dropping all values.
+    pub fn clear(&mut self) {
+        use core::ptr::{drop_in_place, slice_from_raw_parts_mut};
+
+        if self.count > 0 && core::mem::needs_drop::<T>() {
+            let first_slot = self.physical_add(0);
+            let last_slot = self.physical_add(self.count);
+            if first_slot < last_slot {
+                // elements are in one contiguous block
+                unsafe {
+                    drop_in_place(slice_from_raw_parts_mut(
+                        self.buffer.add(first_slot),
+                        last_slot - first_slot,
+                    ));
+                }
+            } else {
+                // elements wrap around the end of the buffer
+                unsafe {
+                    drop_in_place(slice_from_raw_parts_mut(
+                        self.buffer.add(first_slot),
+                        self.capacity - first_slot,
+                    ));
+                    // check if first and last are at the start of the array
+                    if first_slot != last_slot || first_slot != 0 {
+                        drop_in_place(slice_from_raw_parts_mut(self.buffer, last_slot));
+                    }
+                }
+            }
+        }
+        self.head = 0;
+        self.count = 0;
+    }
+
+    /// Return the number of elements in the array.
+    pub fn len(&self) -> usize {
+        self.count
+    }
+
+    /// Returns the total number of elements the cyclic array can hold.
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    /// Returns true if the array has a length of 0.
+    pub fn is_empty(&self) -> bool {
+        self.count == 0
+    }
+
+    /// Returns true if the array has a length equal to its capacity.
+    pub fn is_full(&self) -> bool {
+        self.count == self.capacity
+    }
+
+    /// Perform a wrapping addition relative to the head of the array and
+    /// convert the logical offset to the physical offset within the array.
+    fn physical_add(&self, addend: usize) -> usize {
+        let logical_index = self.head.wrapping_add(addend);
+        if logical_index >= self.capacity {
+            logical_index - self.capacity
+        } else {
+            logical_index
+        }
+    }
+
+    /// Perform a wrapping subtraction relative to the head of the array and
+    /// convert the logical offset to the physical offset within the array.
+    fn physical_sub(&self, subtrahend: usize) -> usize {
+        let logical_index = self
+            .head
+            .wrapping_sub(subtrahend)
+            .wrapping_add(self.capacity);
+        if logical_index >= self.capacity {
+            logical_index - self.capacity
+        } else {
+            logical_index
+        }
+    }
+}
+
+impl<T> Default for CyclicArray<T> {
+    fn default() -> Self {
+        Self::new(0)
+    }
+}
+
+impl<T, A: Allocator> fmt::Display for CyclicArray<T, A> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "CyclicArray(capacity: {}, head: {}, count: {})",
+            self.capacity, self.head, self.count,
+        )
+    }
+}
+
+impl<T: fmt::Debug, A: Allocator> fmt::Debug for CyclicArray<T, A> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let (first, second) = self.as_slices();
+        f.debug_list()
+            .entries(first.iter().chain(second.iter()))
+            .finish()
+    }
+}
+
+impl<T, A: Allocator> Index<usize> for CyclicArray<T, A> {
+    type Output = T;
+
+    fn index(&self, index: usize) -> &Self::Output {
+        let Some(item) = self.get(index) else {
+            panic!("index out of bounds: {}", index);
+        };
+        item
+    }
+}
+
+impl<T, A: Allocator> IndexMut<usize> for CyclicArray<T, A> {
+    fn index_mut(&mut self, index: usize) -> &mut Self::Output {
+        let Some(item) = self.get_mut(index) else {
+            panic!("index out of bounds: {}", index);
+        };
+        item
+    }
+}
+
+impl<T: Clone, A: Allocator + Clone> Clone for CyclicArray<T, A> {
+    fn clone(&self) -> Self {
+        // place each element in the same physical slot as the original
+        let mut cloned: CyclicArray<T, A> = CyclicArray::new_in(self.capacity, self.alloc.clone());
+        cloned.head = self.head;
+        let (first, second) = self.as_slices();
+        for value in first.iter().chain(second.iter()) {
+            let slot = cloned.physical_add(cloned.count);
+            unsafe { core::ptr::write(cloned.buffer.add(slot), value.clone()) };
+            cloned.count += 1;
+        }
+        cloned
+    }
+}
+
+impl<T, A: Allocator> Drop for CyclicArray<T, A> {
+    fn drop(&mut self) {
+        self.clear();
+        self.dealloc();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::{HashMap, HashSet};
+
+    #[test]
+    fn test_vector_insert_head() {
+        let mut sut = Vector::<usize>::new();
+        assert!(sut.is_empty());
+        for value in (1..=16).rev() {
+            sut.insert(0, value);
+        }
+        assert!(!sut.is_empty());
+        for (index, value) in (1..=16).enumerate() {
+            assert_eq!(sut[index], value);
+        }
+    }
+
+    #[test]
+    fn test_vector_push_and_clear() {
+        let mut sut = Vector::<usize>::new();
+        assert!(sut.is_empty());
+        for value in 0..64 {
+            sut.push(value);
+        }
+        assert!(!sut.is_empty());
+        assert_eq!(sut.len(), 64);
+        assert_eq!(sut.capacity(), 64);
+        for value in 0..64 {
+            assert_eq!(sut[value], value);
+        }
+        sut.clear();
+        assert!(sut.is_empty());
+        assert_eq!(sut.len(), 0);
+        assert_eq!(sut.capacity(), 0);
+    }
+
+    #[test]
+    fn test_vector_get_mut() {
+        let mut sut = Vector::<usize>::new();
+        for value in 0..4 {
+            sut.push(value);
+        }
+        if let Some(value) = sut.get_mut(1) {
+            *value = 11;
+        } else {
+            panic!("get_mut() returned None")
+        }
+        sut[2] = 12;
+        assert_eq!(sut.len(), 4);
+        assert_eq!(sut[0], 0);
+        assert_eq!(sut[1], 11);
+        assert_eq!(sut[2], 12);
+        assert_eq!(sut[3], 3);
+    }
+
+    #[test]
+    fn test_vector_insert_expand() {
+        let mut sut = Vector::<usize>::new();
+        assert!(sut.is_empty());
+        for value in (1..=130).rev() {
+            sut.insert(0, value);
+        }
+        assert!(!sut.is_empty());
+        assert_eq!(sut.len(), 130);
+        assert_eq!(sut.capacity(), 144);
+        for value in 0..130 {
+            assert_eq!(sut[value], value + 1);
+        }
+    }
+
+    #[test]
+    fn test_vector_insert_growth_boundaries() {
+        let mut sut = Vector::<usize>::new();
+        // len == capacity: a single block is allocated
+        sut.push(0);
+        assert_eq!(sut.capacity(), 4);
+        for value in 1..4 {
+            sut.push(value);
+            assert_eq!(sut.capacity(), 4);
+        }
+        sut.insert(0, 100);
+        assert_eq!(sut.capacity(), 8);
+        // fill to the upper limit (16) with the capacity equal to the length
+        for value in 5..16 {
+            sut.push(value);
+        }
+        assert_eq!(sut.len(), 16);
+        assert_eq!(sut.capacity(), 16);
+        // len == upper_limit and len == capacity: expand, then add one block
+        sut.insert(8, 200);
+        assert_eq!(sut.len(), 17);
+        assert_eq!(sut.capacity(), 24);
+        // within capacity after expansion: no allocation
+        for value in 18..=24 {
+            sut.push(value);
+            assert_eq!(sut.capacity(), 24);
+        }
+        sut.push(25);
+        assert_eq!(sut.capacity(), 32);
+        assert_eq!(sut[0], 100);
+        assert_eq!(sut[8], 200);
+    }
+
+    #[cfg(feature = "metrics")]
+    #[test]
+    fn test_vector_alloc_stats() {
+        let mut sut = Vector::<u32>::new();
+        assert_eq!(sut.alloc_stats(), AllocStats::default());
+        // four blocks of 4 elements
+        for value in 0..16 {
+            sut.push(value);
+        }
+        let stats = sut.alloc_stats();
+        assert_eq!(stats.allocs, 4);
+        assert_eq!(stats.deallocs, 0);
+        assert_eq!(stats.bytes_allocated, 64);
+        // expand combines them into two blocks of 8, then adds a third
+        sut.push(16);
+        let stats = sut.alloc_stats();
+        assert_eq!(stats.allocs, 7);
+        assert_eq!(stats.deallocs, 4);
+        assert_eq!(stats.bytes_allocated, 64 + 64 + 32);
+        // popping the element in the last block releases that block
+        sut.pop();
+        let stats = sut.alloc_stats();
+        assert_eq!(stats.allocs, 7);
+        assert_eq!(stats.deallocs, 5);
+        // pushing again allocates once more
+        sut.push(16);
+        assert_eq!(sut.alloc_stats().allocs, 8);
+        sut.clear();
+        let stats = sut.alloc_stats();
+        assert_eq!(stats.allocs, stats.deallocs);
+    }
+
+    #[cfg(feature = "metrics")]
+    #[test]
+    fn test_vector_alloc_stats_retained() {
+        // every block allocated and not yet deallocated is still held
+        fn live(sut: &Vector<usize>) -> usize {
+            let stats = sut.alloc_stats();
+            stats.allocs - stats.deallocs
+        }
+        let mut sut: Vector<usize> = (0..1000).rev().collect();
+        let before = sut.alloc_stats();
+        assert!(before.allocs > 0);
+        sut.sort();
+        assert_eq!(sut.alloc_stats(), before);
+        sut.extend_front(0..500);
+        assert!(sut.alloc_stats().allocs >= before.allocs);
+        assert!(sut.alloc_stats().deallocs >= before.deallocs);
+        assert_eq!(live(&sut), sut.index.len());
+
+        let mut vec: Vec<usize> = (0..100).collect();
+        sut.swap_with_vec(&mut vec);
+        assert!(sut.alloc_stats().allocs >= before.allocs);
+        assert_eq!(live(&sut), sut.index.len());
+
+        // counters follow the blocks taken from other vectors
+        let mut other: Vector<usize> = Vector::with_tier_exponent(sut.tier_info().k);
+        other.extend(0..50);
+        let theirs = other.alloc_stats();
+        let ours = sut.alloc_stats();
+        sut.append(&mut other);
+        assert_eq!(sut.alloc_stats().allocs, ours.allocs + theirs.allocs);
+        assert_eq!(live(&sut), sut.index.len());
+        assert_eq!(other.alloc_stats(), AllocStats::default());
+
+        let mut empty: Vector<usize> = Vector::new();
+        let mut other: Vector<usize> = (0..50).collect();
+        empty.append(&mut other);
+        assert_eq!(live(&empty), empty.index.len());
+        assert_eq!(live(&other), other.index.len());
+
+        let packed = Vector::pack(vec![sut, empty]);
+        assert_eq!(live(&packed), packed.index.len());
+        let parts: Vec<Vector<usize>> = (0..4)
+            .map(|_| {
+                let mut part = Vector::with_tier_exponent(4);
+                part.extend(0..16);
+                part
+            })
+            .collect();
+        let concat = Vector::concat_vectors(parts);
+        assert_eq!(concat.alloc_stats().allocs, 4);
+        assert_eq!(live(&concat), concat.index.len());
+    }
+
+    #[cfg(feature = "metrics")]
+    #[test]
+    fn test_vector_alloc_stats_split_off() {
+        fn live(sut: &Vector<usize>) -> usize {
+            let stats = sut.alloc_stats();
+            stats.allocs - stats.deallocs
+        }
+        // on a block boundary, with spare blocks at the end
+        let mut sut: Vector<usize> = (0..1000).collect();
+        sut.reserve(500);
+        let mut other = sut.split_off(512);
+        assert_eq!(live(&sut), sut.tier_info().block_count);
+        assert_eq!(live(&other), other.tier_info().block_count);
+        assert!(other.iter().copied().eq(512..1000));
+
+        // within a block, leaving the spare blocks empty
+        let mut third = other.split_off(100);
+        assert_eq!(live(&other), other.tier_info().block_count);
+        assert_eq!(live(&third), third.tier_info().block_count);
+        assert!(third.iter().copied().eq(612..1000));
+
+        sut.append(&mut third);
+        assert_eq!(live(&sut), sut.tier_info().block_count);
+        assert_eq!(live(&third), third.tier_info().block_count);
+        let packed = Vector::pack(vec![sut, other]);
+        assert_eq!(live(&packed), packed.tier_info().block_count);
+        assert_eq!(packed.len(), 1000);
+    }
+
+    #[test]
+    fn test_vector_push_many() {
+        let mut sut = Vector::<usize>::new();
+        assert!(sut.is_empty());
+        for value in 0..100_000 {
+            sut.push(value);
+        }
+        assert!(!sut.is_empty());
+        assert_eq!(sut.len(), 100_000);
+        assert_eq!(sut.capacity(), 100352);
+        for value in 0..100_000 {
+            assert_eq!(sut[value], value);
+        }
+    }
+
+    #[test]
+    fn test_vector_push_within_capacity() {
+        // empty array has no allocated space
+        let mut sut = Vector::<u32>::new();
+        assert_eq!(sut.push_within_capacity(101), Err(101));
+        sut.push(1);
+        sut.push(2);
+        assert_eq!(sut.push_within_capacity(3), Ok(()));
+        assert_eq!(sut.push_within_capacity(4), Ok(()));
+        assert_eq!(sut.push_within_capacity(5), Err(5));
+    }
+
+    #[test]
+    fn test_vector_push_until_full() {
+        // empty array has no allocated space
+        let mut sut = Vector::<usize>::new();
+        let mut rest = sut.push_until_full(0..10);
+        assert_eq!(rest.next(), Some(0));
+        assert!(sut.is_empty());
+
+        // fill the remaining space of the one allocated block
+        sut.push(0);
+        assert_eq!(sut.capacity(), 4);
+        let rest = sut.push_until_full(1..10);
+        assert_eq!(sut.len(), 4);
+        for value in 0..4 {
+            assert_eq!(sut[value], value);
+        }
+        let leftover: Vec<usize> = rest.collect();
+        assert_eq!(leftover, vec![4, 5, 6, 7, 8, 9]);
+
+        // iterator that fits entirely within the spare capacity
+        sut.push(4);
+        let mut rest = sut.push_until_full(5..7);
+        assert_eq!(rest.next(), None);
+        assert_eq!(sut.len(), 7);
+        assert_eq!(sut[6], 6);
+    }
+
+    #[test]
+    fn test_vector_remove_small() {
+        let mut sut = Vector::<usize>::new();
+        assert!(sut.is_empty());
+        assert_eq!(sut.len(), 0);
+        for value in 0..15 {
+            sut.push(value);
+        }
+        assert!(!sut.is_empty());
+        assert_eq!(sut.len(), 15);
+        for value in 0..15 {
+            assert_eq!(sut.remove(0), value);
+        }
+        assert!(sut.is_empty());
+        assert_eq!(sut.len(), 0);
+        assert_eq!(sut.capacity(), 0);
+    }
+
+    #[test]
+    fn test_vector_remove_medium() {
+        let mut sut = Vector::<usize>::new();
+        assert!(sut.is_empty());
+        assert_eq!(sut.len(), 0);
+        assert_eq!(sut.capacity(), 0);
+        for value in 0..2048 {
+            sut.push(value);
+        }
+        assert!(!sut.is_empty());
+        assert_eq!(sut.len(), 2048);
+        assert_eq!(sut.capacity(), 2048);
+        for value in 0..2048 {
+            assert_eq!(sut.remove(0), value);
+        }
+        assert!(sut.is_empty());
+        assert_eq!(sut.len(), 0);
+        assert_eq!(sut.capacity(), 0);
+    }
+
+    #[test]
+    fn test_vector_expand_and_compress() {
+        // add enough to cause multiple expansions
+        let mut sut = Vector::<usize>::new();
+        for value in 0..1024 {
+            sut.push(value);
+        }
+        assert_eq!(sut.len(), 1024);
+        assert_eq!(sut.capacity(), 1024);
+        // remove enough to cause multiple compressions
+        for _ in 0..960 {
+            sut.pop();
+        }
+        // ensure the correct elements remain
+        assert_eq!(sut.len(), 64);
+        assert_eq!(sut.capacity(), 64);
+        for value in 0..64 {
+            assert_eq!(sut[value], value);
+        }
+    }
+
+    #[test]
+    fn test_vector_shrink_dope() {
+        let mut sut: Vector<usize> = (0..100_000).collect();
+        // remove blocks without going so far as to compress the vector
+        for _ in 0..67_000 {
+            sut.pop();
+        }
+        assert!(sut.index.capacity() > 2 * sut.index.len());
+        sut.shrink_dope();
+        assert_eq!(sut.index.capacity(), sut.index.len());
+        for value in 0..33_000 {
+            assert_eq!(sut[value], value);
+        }
+    }
+
+    #[test]
+    fn test_vector_pop_small() {
+        let mut sut = Vector::<usize>::new();
+        assert!(sut.is_empty());
+        assert_eq!(sut.len(), 0);
+        for value in 0..15 {
+            sut.push(value);
+        }
+        assert!(!sut.is_empty());
+        assert_eq!(sut.len(), 15);
+        for value in (0..15).rev() {
+            assert_eq!(sut.pop(), Some(value));
+        }
+        assert!(sut.is_empty());
+        assert_eq!(sut.len(), 0);
+        assert_eq!(sut.capacity(), 0);
+    }
+
+    #[test]
+    fn test_vector_pop_back_n_vec() {
+        let mut sut: Vector<usize> = (0..1000).collect();
+        let popped = sut.pop_back_n_vec(300);
+        assert_eq!(popped, (700..1000).collect::<Vec<usize>>());
+        assert_eq!(sut.len(), 700);
+        for value in 0..700 {
+            assert_eq!(sut[value], value);
+        }
+        assert!(sut.pop_back_n_vec(0).is_empty());
+        let popped = sut.pop_back_n_vec(5000);
+        assert_eq!(popped, (0..700).collect::<Vec<usize>>());
+        assert!(sut.is_empty());
+        assert_eq!(sut.capacity(), 0);
+    }
+
+    #[test]
+    fn test_vector_pop_if() {
+        let mut sut = Vector::<u32>::new();
+        assert!(sut.pop_if(|_| panic!("should not be called")).is_none());
+        for value in 0..10 {
+            sut.push(value);
+        }
+        assert!(sut.pop_if(|_| false).is_none());
+        let maybe = sut.pop_if(|v| *v == 9);
+        assert_eq!(maybe.unwrap(), 9);
+        assert!(sut.pop_if(|v| *v == 9).is_none());
+    }
+
+    #[test]
+    fn test_vector_iter() {
+        let mut sut = Vector::<usize>::new();
+        for value in 0..1000 {
+            sut.push(value);
+        }
+        assert_eq!(sut.len(), 1000);
+        for (index, value) in sut.iter().enumerate() {
+            assert_eq!(sut[index], *value);
+        }
+    }
+
+    #[test]
+    fn test_vector_reserve_uninit_single_block() {
+        let mut sut = Vector::<usize>::new();
+        sut.push(0);
+        let runs = unsafe { sut.reserve_uninit(3) };
+        assert_eq!(runs.len(), 1);
+        assert_eq!(runs[0].1, 3);
+        for offset in 0..3 {
+            unsafe { std::ptr::write(runs[0].0.add(offset), offset + 1) };
+        }
+        unsafe { sut.commit_len(4) };
+        assert_eq!(sut.len(), 4);
+        for value in 0..4 {
+            assert_eq!(sut[value], value);
+        }
+        sut.push(4);
+        assert_eq!(sut[4], 4);
+    }
+
+    #[test]
+    fn test_vector_reserve_uninit_many_blocks() {
+        let mut sut = Vector::<String>::new();
+        for value in 0..10 {
+            sut.push(value.to_string());
+        }
+        // spans the tail of the last block, several new blocks, and expansion
+        let runs = unsafe { sut.reserve_uninit(90) };
+        assert!(runs.len() > 2);
+        assert_eq!(runs.iter().map(|(_, len)| len).sum::<usize>(), 90);
+        assert!(sut.capacity() >= 100);
+        let mut value = 10;
+        for (ptr, len) in runs {
+            for offset in 0..len {
+                unsafe { std::ptr::write(ptr.add(offset), value.to_string()) };
+                value += 1;
+            }
+        }
+        unsafe { sut.commit_len(100) };
+        assert_eq!(sut.len(), 100);
+        for value in 0..100 {
+            assert_eq!(sut[value], value.to_string());
+        }
+        // ordinary operations continue to work after the bulk load
+        sut.insert(0, "first".to_owned());
+        assert_eq!(sut.remove(0), "first");
+        while let Some(s) = sut.pop() {
+            assert!(!s.is_empty());
+        }
+        assert_eq!(sut.capacity(), 0);
+    }
+
+    #[test]
+    fn test_vector_reserve_uninit_wrapped() {
+        let mut sut = Vector::<usize>::new();
+        // inserting at the head leaves the last block wrapped around
+        for value in (0..6).rev() {
+            sut.insert(0, value);
+        }
+        let runs = unsafe { sut.reserve_uninit(6) };
+        assert_eq!(runs.iter().map(|(_, len)| len).sum::<usize>(), 6);
+        let mut value = 6;
+        for (ptr, len) in runs {
+            for offset in 0..len {
+                unsafe { std::ptr::write(ptr.add(offset), value) };
+                value += 1;
+            }
+        }
+        unsafe { sut.commit_len(12) };
+        for value in 0..12 {
+            assert_eq!(sut[value], value);
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "new length (is 9) should be <= capacity (is 8)")]
+    fn test_vector_commit_len_panics() {
+        let mut sut = Vector::<usize>::new();
+        let _ = unsafe { sut.reserve_uninit(8) };
+        unsafe { sut.commit_len(9) };
+    }
+
+    #[test]
+    fn test_vector_reserve_initialized() {
+        let mut sut: Vector<u32> = Vector::new();
+        sut.reserve_initialized(100);
+        assert_eq!(sut.len(), 100);
+        assert!(sut.iter().all(|v| *v == 0));
+        sut[42] = 42;
+
+        let mut sut: Vector<String> = (0..10).map(|v| v.to_string()).collect();
+        sut.reserve_initialized(25);
+        assert_eq!(sut.len(), 35);
+        for value in 0..10 {
+            assert_eq!(sut[value], value.to_string());
+        }
+        assert!(sut.iter().skip(10).all(|v| v.is_empty()));
+        sut.reserve_initialized(0);
+        assert_eq!(sut.len(), 35);
+    }
+
+    #[test]
+    fn test_vector_with_len() {
+        let sut = Vector::<u32>::with_len(10_000);
+        assert_eq!(sut.len(), 10_000);
+        // blocks of 128 elements, only as many as needed
+        assert_eq!(sut.capacity(), 10_112);
+        assert_eq!(sut.min_block_fill(), Some(1.0));
+        assert!(sut.iter().all(|v| *v == 0));
+
+        let mut sut = Vector::<String>::with_len(100);
+        assert_eq!(sut.len(), 100);
+        assert!(sut.iter().all(|v| v.is_empty()));
+        sut[99] = "last".to_owned();
+        assert_eq!(sut[99], "last");
+
+        assert!(Vector::<u32>::with_len(0).is_empty());
+    }
+
+    #[test]
+    fn test_vector_find_subslice() {
+        let mut sut = Vector::<usize>::new();
+        for value in 0..64 {
+            sut.push(value);
+        }
+        // blocks are 8 elements wide, the needle straddles the first boundary
+        assert_eq!(sut.find_subslice(&[6, 7, 8, 9]), Some(6));
+        assert_eq!(sut.find_subslice(&[7, 6]), None);
+        assert_eq!(sut.find_subslice(&[62, 63]), Some(62));
+        assert_eq!(sut.find_subslice(&[63, 64]), None);
+        assert_eq!(sut.find_subslice(&[]), Some(0));
+        let empty = Vector::<usize>::new();
+        assert_eq!(empty.find_subslice(&[]), Some(0));
+        assert_eq!(empty.find_subslice(&[1]), None);
+    }
+
+    #[test]
+    fn test_vector_merge_sorted() {
+        let a: Vector<usize> = (0..100).map(|v| v * 3).collect();
+        let b: Vector<usize> = (0..40).map(|v| v * 2).collect();
+        let sut = a.merge_sorted(b);
+        assert_eq!(sut.len(), 140);
+        let mut expected: Vec<usize> = (0..100).map(|v| v * 3).collect();
+        expected.extend((0..40).map(|v| v * 2));
+        expected.sort();
+        for (index, value) in expected.iter().enumerate() {
+            assert_eq!(sut[index], *value);
+        }
+
+        // equal elements from the first vector come first
+        let a: Vector<KeyOnly> = [(1, 'a'), (2, 'a'), (2, 'b')]
+            .map(KeyOnly)
+            .into_iter()
+            .collect();
+        let b: Vector<KeyOnly> = [(0, 'c'), (2, 'c'), (3, 'c')]
+            .map(KeyOnly)
+            .into_iter()
+            .collect();
+        let merged: Vec<(u32, char)> = a.merge_sorted(b).into_iter().map(|k| k.0).collect();
+        assert_eq!(
+            merged,
+            vec![(0, 'c'), (1, 'a'), (2, 'a'), (2, 'b'), (2, 'c'), (3, 'c')]
+        );
+
+        let empty: Vector<usize> = Vector::new();
+        let sut = empty.merge_sorted((0..5).collect());
+        assert_eq!(sut.len(), 5);
+
+        // the combined length is reserved up front, avoiding any expansion
+        let a: Vector<usize> = (0..3000).map(|v| v * 2).collect();
+        let b: Vector<usize> = (0..2000).map(|v| v * 2 + 1).collect();
+        let sut = a.merge_sorted(b);
+        // expanding would have released the smaller blocks
+        #[cfg(feature = "metrics")]
+        assert_eq!(sut.alloc_stats().deallocs, 0);
+        assert_eq!(sut.len(), 5000);
+        assert!(sut.is_sorted());
+    }
+
+    /// Orders only by the first field so that stability can be observed.
+    #[derive(Debug)]
+    struct KeyOnly((u32, char));
+
+    impl PartialEq for KeyOnly {
+        fn eq(&self, other: &Self) -> bool {
+            self.0.0 == other.0.0
+        }
+    }
+
+    impl Eq for KeyOnly {}
+
+    impl PartialOrd for KeyOnly {
+        fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+            Some(self.cmp(other))
+        }
+    }
+
+    impl Ord for KeyOnly {
+        fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+            self.0.0.cmp(&other.0.0)
+        }
+    }
+
+    #[test]
+    fn test_vector_min_block_fill() {
+        let mut sut = Vector::<usize>::new();
+        assert_eq!(sut.min_block_fill(), None);
+        for value in 0..4 {
+            sut.push(value);
+        }
+        assert_eq!(sut.min_block_fill(), None);
+        for value in 4..1000 {
+            sut.push(value);
+        }
+        for _ in 0..100 {
+            let from = rand::random_range(0..sut.len());
+            let to = rand::random_range(0..sut.len() - 1);
+            let value = sut.remove(from);
+            sut.insert(to, value);
+        }
+        assert_eq!(sut.min_block_fill(), Some(1.0));
+
+        // deliberately under-fill a block in the middle
+        let l = sut.l;
+        for _ in 0..(l / 4) {
+            sut.index[1].pop_back();
+        }
+        assert_eq!(sut.min_block_fill(), Some(0.75));
+        sut.index[2].clear();
+        assert_eq!(sut.min_block_fill(), Some(0.0));
+    }
+
+    #[test]
+    fn test_vector_from_slices() {
+        let first: Vec<String> = (0..3).map(|v| v.to_string()).collect();
+        let second: Vec<String> = (3..40).map(|v| v.to_string()).collect();
+        let third: Vec<String> = (40..41).map(|v| v.to_string()).collect();
+        let slices: Vec<&[String]> = vec![&first, &[], &second, &[], &third];
+        let sut = Vector::from_slices(slices);
+        assert_eq!(sut.len(), 41);
+        for value in 0..41 {
+            assert_eq!(sut[value], value.to_string());
+        }
+
+        // iterator without an exact size hint
+        let numbers: Vec<usize> = (0..1000).collect();
+        let sut = Vector::from_slices(numbers.chunks(7).filter(|c| c[0] % 2 == 0));
+        let expected: Vec<usize> = numbers
+            .chunks(7)
+            .filter(|c| c[0] % 2 == 0)
+            .flatten()
+            .copied()
+            .collect();
+        assert_eq!(sut.len(), expected.len());
+        for (index, value) in expected.iter().enumerate() {
+            assert_eq!(sut[index], *value);
+        }
+
+        let empty: Vec<&[usize]> = vec![];
+        assert!(Vector::from_slices(empty).is_empty());
+    }
+
+    #[test]
+    fn test_vector_swap_with_vec() {
+        let mut sut: Vector<String> = (0..5000).map(|v| v.to_string()).collect();
+        let mut other: Vec<String> = (0..3000).map(|v| format!("v{v}")).collect();
+        sut.swap_with_vec(&mut other);
+        assert_eq!(sut.len(), 3000);
+        assert_eq!(other.len(), 5000);
+        for value in 0..3000 {
+            assert_eq!(sut[value], format!("v{value}"));
+        }
+        for (index, value) in other.iter().enumerate() {
+            assert_eq!(*value, index.to_string());
+        }
+
+        let mut empty: Vec<String> = Vec::new();
+        sut.swap_with_vec(&mut empty);
+        assert!(sut.is_empty());
+        assert_eq!(empty.len(), 3000);
+
+        // the block size of the vector is retained
+        let mut sut: Vector<usize> = Vector::with_tier_exponent(6);
+        sut.extend(0..100);
+        let mut other: Vec<usize> = (100..300).collect();
+        sut.swap_with_vec(&mut other);
+        assert!(sut.iter().copied().eq(100..300));
+        assert!(other.iter().copied().eq(0..100));
+        assert_eq!(sut.tier_info().k, 6);
+    }
+
+    #[test]
+    fn test_vector_swap_with_vec_shrinks() {
+        let mut sut: Vector<usize> = (0..10_000).collect();
+        assert_eq!(sut.tier_info().k, 7);
+        let mut other: Vec<usize> = (0..10).collect();
+        sut.swap_with_vec(&mut other);
+        assert!(sut.iter().copied().eq(0..10));
+        assert_eq!(other.len(), 10_000);
+        assert_eq!(sut.tier_info().k, 2);
+        assert_eq!(sut.capacity(), 12);
+
+        // and grows again when swapping back
+        sut.swap_with_vec(&mut other);
+        assert!(sut.iter().copied().eq(0..10_000));
+        assert_eq!(sut.tier_info().k, 7);
+        assert_eq!(sut.capacity(), 10_112);
+
+        // blocks that suit the new length are kept, less any left empty
+        let mut other: Vec<usize> = (0..3000).collect();
+        sut.swap_with_vec(&mut other);
+        assert_eq!(sut.tier_info().k, 7);
+        assert_eq!(sut.capacity(), 3072);
+    }
+
+    #[cfg(feature = "arbitrary")]
+    #[test]
+    fn test_vector_arbitrary() {
+        use arbitrary::{Arbitrary, Unstructured};
+
+        let bytes: Vec<u8> = (0..=255).collect();
+        let mut u = Unstructured::new(&bytes);
+        let sut = Vector::<u16>::arbitrary(&mut u).unwrap();
+        assert_eq!(sut.len(), 127);
+        for (index, value) in sut.iter().enumerate() {
+            let low = (index * 2) as u8;
+            assert_eq!(*value, u16::from_le_bytes([low, low + 1]));
+        }
+
+        // each element is preceded by a byte indicating whether to continue
+        let u = Unstructured::new(&[1, 10, 1, 20, 1, 30, 0, 40]);
+        let rest = Vector::<u8>::arbitrary_take_rest(u).unwrap();
+        assert_eq!(rest.len(), 3);
+        assert_eq!(rest[0], 10);
+        assert_eq!(rest[1], 20);
+        assert_eq!(rest[2], 30);
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_vector_set_operations() {
+        let a: Vector<usize> = (0..20).collect();
+        let b: Vector<usize> = (10..30).rev().collect();
+        let diff: Vec<usize> = a.difference(&b).into_iter().collect();
+        assert_eq!(diff, (0..10).collect::<Vec<usize>>());
+        let inter: Vec<usize> = a.intersection(&b).into_iter().collect();
+        assert_eq!(inter, (10..20).collect::<Vec<usize>>());
+        let union: Vec<usize> = a.union(&b).into_iter().collect();
+        let mut expected: Vec<usize> = (0..20).collect();
+        expected.extend((20..30).rev());
+        assert_eq!(union, expected);
+
+        // disjoint inputs
+        let c: Vector<usize> = (100..110).collect();
+        assert_eq!(a.difference(&c).len(), 20);
+        assert!(a.intersection(&c).is_empty());
+        assert_eq!(a.union(&c).len(), 30);
+
+        // identical inputs
+        assert!(a.difference(&a).is_empty());
+        let inter: Vec<usize> = a.intersection(&a).into_iter().collect();
+        assert_eq!(inter, (0..20).collect::<Vec<usize>>());
+        let union: Vec<usize> = a.union(&a).into_iter().collect();
+        assert_eq!(union, (0..20).collect::<Vec<usize>>());
+    }
+
+    #[test]
+    fn test_vector_find_copy() {
+        let mut sut: Vector<u32> = (0..10_000).collect();
+        // wrap some of the blocks around
+        sut.insert(0, 20_000);
+        sut.remove(5000);
+        let baseline = |target: u32| sut.iter().position(|v| *v == target);
+        for target in [20_000, 0, 1, 15, 16, 17, 127, 128, 4998, 5000, 5001, 9999] {
+            assert_eq!(sut.find_u32(target), baseline(target), "{target}");
+            assert!(sut.find_u32(target).is_some());
+        }
+        for target in [4999, 10_000, u32::MAX] {
+            assert_eq!(sut.find_u32(target), None);
+        }
+        let wide: Vector<u64> = (0..1000).map(|v| v * 3).collect();
+        assert_eq!(wide.find_copy(999), Some(333));
+        assert_eq!(wide.find_copy(1000), None);
+        assert_eq!(Vector::<u32>::new().find_u32(0), None);
+    }
+
+    #[test]
+    fn test_find_u32_in_slice() {
+        // the vectorized search agrees with the scalar search for every length,
+        // match position, and with repeated matches
+        for len in 0..70 {
+            let mut values: Vec<u32> = (0..len as u32).collect();
+            for target in 0..=len as u32 {
+                let expected = values.iter().position(|v| *v == target);
+                assert_eq!(find_u32_in_slice(&values, target), expected);
+                assert_eq!(find_in_slice(&values, target), expected);
+            }
+            if len > 0 {
+                values[len / 2] = 0;
+                values[len - 1] = 0;
+                assert_eq!(find_u32_in_slice(&values, 0), Some(0));
+                assert_eq!(
+                    find_u32_in_slice(&values[1..], 0),
+                    find_in_slice(&values[1..], 0)
+                );
+            }
+        }
+        let values: Vec<u32> = (0..1000).map(|_| rand::random_range(0..50)).collect();
+        for target in [0, 1, 25, 49, 50, u32::MAX] {
+            assert_eq!(
+                find_u32_in_slice(&values, target),
+                find_in_slice(&values, target)
+            );
+        }
+    }
+
+    #[test]
+    fn test_vector_split_into() {
+        for (len, parts) in [(100, 4), (100, 7), (3, 5), (0, 2), (1000, 1)] {
+            let sut: Vector<usize> = (0..len).collect();
+            let split = sut.split_into(parts);
+            assert_eq!(split.len(), parts);
+            let lengths: Vec<usize> = split.iter().map(|v| v.len()).collect();
+            let min = *lengths.iter().min().unwrap();
+            let max = *lengths.iter().max().unwrap();
+            assert!(max - min <= 1, "{lengths:?}");
+            // longer parts come first
+            assert!(lengths.windows(2).all(|w| w[0] >= w[1]));
+            for part in split.iter() {
+                assert!(part.min_block_fill().is_none_or(|fill| fill == 1.0));
+            }
+            let joined: Vec<usize> = split.into_iter().flatten().collect();
+            assert_eq!(joined, (0..len).collect::<Vec<usize>>());
+        }
+    }
+
+    #[cfg(feature = "metrics")]
+    #[test]
+    fn test_vector_split_into_moves_blocks() {
+        // 16 blocks of 16 elements, split on block boundaries
+        let mut sut: Vector<usize> = Vector::with_tier_exponent(4);
+        sut.extend(0..256);
+        let split = sut.split_into(4);
+        for part in split.iter() {
+            // the counts of the moved blocks go with them
+            assert_eq!(part.tier_info().block_count, 4);
+            assert_eq!(part.alloc_stats().allocs, 4);
+        }
+
+        // parts that straddle blocks allocate only the block at the boundary
+        let mut sut: Vector<usize> = Vector::with_tier_exponent(4);
+        sut.extend(0..256);
+        let split = sut.split_into(3);
+        let allocs: usize = split.iter().map(|part| part.alloc_stats().allocs).sum();
+        assert_eq!(allocs, 16 + 2);
+        for part in split.iter() {
+            let stats = part.alloc_stats();
+            assert_eq!(stats.allocs - stats.deallocs, part.tier_info().block_count);
+        }
+        let joined: Vec<usize> = split.into_iter().flatten().collect();
+        assert_eq!(joined, (0..256).collect::<Vec<usize>>());
+    }
+
+    #[test]
+    #[should_panic(expected = "number of parts must be greater than zero")]
+    fn test_vector_split_into_zero_panics() {
+        let sut: Vector<usize> = (0..10).collect();
+        sut.split_into(0);
+    }
+
+    #[test]
+    fn test_vector_extend_front() {
+        let mut sut: Vector<String> = (0..100).map(|v| v.to_string()).collect();
+        let batch: Vec<String> = (100..150).map(|v| v.to_string()).collect();
+        let mut expected: Vec<String> = (0..100).map(|v| v.to_string()).collect();
+        expected.splice(0..0, batch.iter().cloned());
+        sut.extend_front(batch);
+        assert_eq!(sut.len(), 150);
+        for (index, value) in expected.iter().enumerate() {
+            assert_eq!(sut[index], *value);
+        }
+
+        // iterator without a useful size hint, and an empty batch
+        sut.extend_front((0..10).map(|v| format!("x{v}")).filter(|_| true));
+        assert_eq!(sut[0], "x0");
+        assert_eq!(sut[9], "x9");
+        assert_eq!(sut[10], "100");
+        sut.extend_front(std::iter::empty());
+        assert_eq!(sut.len(), 160);
+
+        let mut empty: Vector<usize> = Vector::new();
+        empty.extend_front(0..5);
+        assert_eq!(
+            empty.iter().copied().collect::<Vec<usize>>(),
+            vec![0, 1, 2, 3, 4]
+        );
+
+        // the block size of the vector is retained
+        let mut sut: Vector<usize> = Vector::with_tier_exponent(6);
+        sut.extend(100..200);
+        sut.extend_front(0..100);
+        assert!(sut.iter().copied().eq(0..200));
+        assert_eq!(sut.tier_info().k, 6);
+        assert_eq!(sut.tier_info().block_count, 4);
+    }
+
+    #[test]
+    fn test_vector_extend_front_sizes() {
+        for len in [0, 1, 15, 16, 17, 100] {
+            for added in [1, 15, 16, 17, 33, 100, 300] {
+                for spare in [0, 64] {
+                    let mut sut: Vector<usize> = Vector::with_tier_exponent(4);
+                    sut.extend(added..added + len);
+                    sut.reserve(spare);
+                    sut.extend_front(0..added);
+                    assert!(sut.iter().copied().eq(0..added + len));
+                    // every block but the last in use is full
+                    let used = sut.len().div_ceil(sut.l);
+                    assert!(sut.index[..used - 1].iter().all(|b| b.is_full()));
+                    assert!(sut.index[used..].iter().all(|b| b.is_empty()));
+                    assert!(sut.tier_info().block_count <= sut.l);
+                }
+            }
+        }
+    }
+
+    #[cfg(feature = "metrics")]
+    #[test]
+    fn test_vector_extend_front_in_place() {
+        let mut sut: Vector<usize> = Vector::with_tier_exponent(4);
+        sut.extend(32..64);
+        let before = sut.alloc_stats();
+        // whole blocks are inserted without disturbing the existing blocks
+        sut.extend_front(0..32);
+        assert!(sut.iter().copied().eq(0..64));
+        assert_eq!(sut.alloc_stats().allocs, before.allocs + 2);
+        assert_eq!(sut.alloc_stats().deallocs, before.deallocs);
+
+        // spare blocks are reused for the leading blocks
+        sut.reserve(40);
+        let before = sut.alloc_stats();
+        sut.extend_front((0..40).map(|v| v + 1000));
+        assert_eq!(sut.len(), 104);
+        assert_eq!(sut[39], 1039);
+        assert_eq!(sut[40], 0);
+        assert_eq!(sut.alloc_stats(), before);
+    }
+
+    #[test]
+    fn test_vector_is_sorted() {
+        let mut sut: Vector<usize> = (0..100).collect();
+        assert!(sut.is_sorted());
+        assert!(!sut.is_sorted_by(|a, b| a >= b));
+        // inversion in the middle of a block (blocks are 16 wide)
+        sut.swap(40, 41);
+        assert!(!sut.is_sorted());
+        sut.swap(40, 41);
+        // inversion across a block boundary
+        sut.swap(47, 48);
+        assert!(!sut.is_sorted());
+        sut.swap(47, 48);
+        assert!(sut.is_sorted());
+
+        let mut empty: Vector<usize> = Vector::new();
+        assert!(empty.is_sorted());
+        empty.push(1);
+        assert!(empty.is_sorted());
+        assert!(empty.is_sorted_by(|_, _| false));
+    }
+
+    #[test]
+    fn test_vector_into_converted() {
+        let sut: Vector<u32> = (0..1000).collect();
+        let wide: Vector<u64> = sut.into_converted();
+        assert_eq!(wide.len(), 1000);
+        for value in 0..1000 {
+            assert_eq!(wide[value], value as u64);
+        }
+
+        let sut: Vector<String> = (0..100).map(|v| v.to_string()).collect();
+        let boxed: Vector<Box<str>> = sut.into_converted();
+        assert_eq!(boxed.len(), 100);
+        for value in 0..100 {
+            assert_eq!(&*boxed[value], value.to_string());
+        }
+    }
+
+    #[test]
+    fn test_vector_sort_stable() {
+        let mut sut: Vector<(u8, usize)> = Vector::new();
+        for sequence in 0..2000 {
+            sut.push((rand::random_range(0..10), sequence));
+        }
+        sut.sort_by_key(|pair| pair.0);
+        assert_eq!(sut.len(), 2000);
+        assert!(sut.is_sorted_by(|a, b| a.0 < b.0 || (a.0 == b.0 && a.1 < b.1)));
+
+        sut.sort_by(|a, b| b.0.cmp(&a.0));
+        assert!(sut.is_sorted_by(|a, b| a.0 > b.0 || (a.0 == b.0 && a.1 < b.1)));
+
+        let mut sut: Vector<String> = (0..500).rev().map(|v| format!("{v:03}")).collect();
+        sut.sort();
+        assert!(sut.is_sorted());
+        assert_eq!(sut[0], "000");
+        assert_eq!(sut[499], "499");
+
+        let mut empty: Vector<usize> = Vector::new();
+        empty.sort();
+        assert!(empty.is_empty());
+
+        // sorting retains the block size and the blocks themselves
+        let mut sut: Vector<usize> = Vector::with_tier_exponent(6);
+        sut.extend((0..100).rev());
+        let capacity = sut.capacity();
+        sut.sort();
+        assert!(sut.iter().copied().eq(0..100));
+        assert_eq!(sut.tier_info().k, 6);
+        assert_eq!(sut.capacity(), capacity);
+    }
+
+    #[test]
+    fn test_vector_as_matrix() {
+        let mut sut: Vector<usize> = (0..12).collect();
+        let matrix = sut.as_matrix(4);
+        assert_eq!(matrix.rows(), 3);
+        assert_eq!(matrix.cols(), 4);
+        assert_eq!(matrix.get(0, 0), Some(&0));
+        assert_eq!(matrix.get(1, 2), Some(&6));
+        assert_eq!(matrix.get(2, 3), Some(&11));
+        assert_eq!(matrix.get(3, 0), None);
+        assert_eq!(matrix.get(0, 4), None);
+        let row: Vec<usize> = matrix.row(1).copied().collect();
+        assert_eq!(row, vec![4, 5, 6, 7]);
+        assert_eq!(matrix.row(3).count(), 0);
+
+        let mut matrix = sut.as_matrix_mut(3);
+        assert_eq!(matrix.rows(), 4);
+        *matrix.get_mut(2, 1).unwrap() = 100;
+        assert!(matrix.get_mut(0, 3).is_none());
+        assert_eq!(matrix.get(2, 1), Some(&100));
+        assert_eq!(sut[7], 100);
+    }
+
+    #[test]
+    #[should_panic(expected = "length (is 12) should be a multiple of cols (is 5)")]
+    fn test_vector_as_matrix_panics() {
+        let sut: Vector<usize> = (0..12).collect();
+        sut.as_matrix(5);
+    }
+
+    #[test]
+    fn test_vector_fold_chunks() {
+        let sut: Vector<usize> = (0..1000).collect();
+        let flat: usize = sut.iter().sum();
+        for chunk_size in [1, 7, 10, 64, 1000, 2000] {
+            let mut chunks = 0;
+            let sum = sut.fold_chunks(chunk_size, 0, |acc, chunk| {
+                assert!(chunk.len() <= chunk_size);
+                chunks += 1;
+                acc + chunk.iter().copied().sum::<usize>()
+            });
+            assert_eq!(sum, flat);
+            assert_eq!(chunks, 1000usize.div_ceil(chunk_size));
+        }
+        let empty: Vector<usize> = Vector::new();
+        assert_eq!(
+            empty.fold_chunks(3, 5, |_, _| panic!("should not be called")),
+            5
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "chunk size must be greater than zero")]
+    fn test_vector_fold_chunks_zero_panics() {
+        let sut: Vector<usize> = (0..10).collect();
+        sut.fold_chunks(0, 0, |acc, _| acc);
+    }
+
+    #[test]
+    fn test_vector_structurally_eq() {
+        let pushed: Vector<usize> = (0..100).collect();
+        let also_pushed: Vector<usize> = (0..100).collect();
+        assert!(pushed.structurally_eq(&also_pushed));
+
+        // same elements, but inserting at the front wraps the blocks
+        let mut wrapped: Vector<usize> = Vector::new();
+        for value in (0..100).rev() {
+            wrapped.insert(0, value);
+        }
+        assert!(pushed.iter().eq(wrapped.iter()));
+        assert!(!pushed.structurally_eq(&wrapped));
+
+        // same layout, different elements
+        let mut changed: Vector<usize> = (0..100).collect();
+        changed[50] = 0;
+        assert!(!pushed.structurally_eq(&changed));
+
+        // different block sizes, as the 65th element causes an expansion
+        let smaller: Vector<usize> = (0..64).collect();
+        let mut larger: Vector<usize> = (0..65).collect();
+        larger.pop();
+        assert!(smaller.iter().eq(larger.iter()));
+        assert!(!smaller.structurally_eq(&larger));
+    }
+
+    #[cfg(feature = "thread")]
+    #[test]
+    fn test_vector_drop_deferred() {
+        use std::sync::Arc;
+        use std::sync::atomic::{AtomicUsize, Ordering};
+
+        struct SlowDrop(Arc<AtomicUsize>);
+
+        impl Drop for SlowDrop {
+            fn drop(&mut self) {
+                std::thread::sleep(std::time::Duration::from_micros(10));
+                self.0.fetch_add(1, Ordering::SeqCst);
+            }
+        }
+
+        let dropped = Arc::new(AtomicUsize::new(0));
+        let mut sut: Vector<SlowDrop> = Vector::new();
+        for _ in 0..1000 {
+            sut.push(SlowDrop(dropped.clone()));
+        }
+        let handle = sut.drop_deferred();
+        handle.join();
+        assert_eq!(dropped.load(Ordering::SeqCst), 1000);
+    }
+
+    #[test]
+    fn test_vector_reshape_to_exponent() {
+        let mut sut: Vector<String> = (0..200).map(|v| v.to_string()).collect();
+        assert_eq!(sut.block_exponent(), 4);
+        // up by several levels
+        assert_eq!(sut.reshape_to_exponent(7), Ok(()));
+        assert_eq!(sut.block_exponent(), 7);
+        assert_eq!(sut.capacity(), 256);
+        for value in 0..200 {
+            assert_eq!(sut[value], value.to_string());
+        }
+        // down by several levels
+        assert_eq!(sut.reshape_to_exponent(4), Ok(()));
+        assert_eq!(sut.block_exponent(), 4);
+        assert_eq!(sut.capacity(), 208);
+        assert_eq!(sut.min_block_fill(), Some(1.0));
+        for value in 0..200 {
+            assert_eq!(sut[value], value.to_string());
+        }
+        // ordinary operations continue to work
+        sut.insert(100, "a".to_owned());
+        assert_eq!(sut.remove(100), "a");
+        assert_eq!(sut.len(), 200);
+
+        // too small for the number of elements
+        assert_eq!(
+            sut.reshape_to_exponent(3),
+            Err(ReshapeError::TooManyElements)
+        );
+        assert_eq!(
+            sut.reshape_to_exponent(1),
+            Err(ReshapeError::ExponentTooSmall)
+        );
+        assert_eq!(
+            sut.reshape_to_exponent(64),
+            Err(ReshapeError::ExponentTooLarge)
+        );
+        assert_eq!(sut.block_exponent(), 4);
+
+        let mut empty: Vector<usize> = Vector::new();
+        assert_eq!(empty.reshape_to_exponent(5), Ok(()));
+        assert_eq!(empty.capacity(), 0);
+        empty.push(1);
+        assert_eq!(empty.capacity(), 32);
+    }
+
+    #[test]
+    fn test_vector_entry() {
+        let mut sut: Vector<usize> = (0..100).collect();
+        // 100 elements are held in blocks of 16
+        for index in [0, 15, 16, 99] {
+            let old = sut.entry(index).unwrap().replace(index * 10);
+            assert_eq!(old, index);
+            assert_eq!(sut[index], index * 10);
+        }
+        let entry = sut.entry(47).unwrap().and_modify(|v| *v += 1);
+        assert_eq!(*entry.get(), 48);
+        *entry.into_mut() += 1;
+        assert_eq!(sut[47], 49);
+        sut.entry(48)
+            .unwrap()
+            .and_modify(|v| *v = 0)
+            .and_modify(|v| *v += 2);
+        assert_eq!(sut[48], 2);
+        let mut entry = sut.entry(1).unwrap();
+        *entry.get_mut() = 7;
+        assert_eq!(sut[1], 7);
+        assert!(sut.entry(100).is_none());
+    }
+
+    #[test]
+    fn test_vector_estimated_move_count() {
+        // physical location of each element, which must be distinct
+        fn locations(sut: &Vector<usize>) -> HashMap<usize, (usize, usize)> {
+            sut.iter_physical()
+                .map(|(block, slot, value)| (*value, (block, slot)))
+                .collect()
+        }
+        let mut sut: Vector<usize> = (0..900).collect();
+        // wrap some blocks around so both shift directions are exercised
+        for value in 0..50 {
+            sut.insert(value * 7, 1000 + value);
+        }
+        let len = sut.len();
+        for index in [0, 1, 31, 32, 63, 64, 500, 519, 520, len - 1, len] {
+            // avoid crossing the expansion threshold
+            assert!(sut.len() < 1024);
+            let estimate = sut.estimated_move_count(index);
+            let before = locations(&sut);
+            sut.insert(index, usize::MAX);
+            let after = locations(&sut);
+            let actual = before
+                .iter()
+                .filter(|(value, at)| after[value] != **at)
+                .count();
+            assert_eq!(estimate, actual, "index {index}");
+            sut.remove(index);
+        }
+        assert!(sut.estimated_move_count(0) > sut.estimated_move_count(len));
+    }
+
+    #[test]
+    fn test_vector_pairs() {
+        let mut sut: Vector<usize> = Vector::new();
+        for value in 0..500 {
+            // insert at the front to wrap the blocks around
+            sut.insert(0, value);
+        }
+        let actual: Vec<(&usize, &usize)> = sut.pairs().collect();
+        let expected: Vec<(&usize, &usize)> = sut.iter().zip(sut.iter().skip(1)).collect();
+        assert_eq!(actual.len(), 499);
+        assert_eq!(actual, expected);
+
+        let mut sut: Vector<usize> = Vector::new();
+        assert_eq!(sut.pairs().count(), 0);
+        sut.push(1);
+        assert_eq!(sut.pairs().count(), 0);
+        sut.push(2);
+        assert_eq!(sut.pairs().collect::<Vec<_>>(), vec![(&1, &2)]);
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_vector_histogram() {
+        let sut: Vector<usize> = (0..100).collect();
+        let histogram = sut.histogram(|x| x % 3);
+        assert_eq!(histogram.len(), 3);
+        assert_eq!(histogram[&0], 34);
+        assert_eq!(histogram[&1], 33);
+        assert_eq!(histogram[&2], 33);
+
+        let empty: Vector<usize> = Vector::new();
+        assert!(empty.histogram(|x| x % 3).is_empty());
+    }
+
+    #[test]
+    fn test_vector_min_max() {
+        let mut values: Vec<i64> = (-500..500).collect();
+        for i in (1..values.len()).rev() {
+            values.swap(i, rand::random_range(0..=i));
+        }
+        let sut: Vector<i64> = values.iter().copied().collect();
+        assert_eq!(sut.min_max(), Some((&-500, &499)));
+        assert_eq!(sut.min_max().unwrap().0, sut.iter().min().unwrap());
+        assert_eq!(sut.min_max().unwrap().1, sut.iter().max().unwrap());
+        assert_eq!(sut.min_by_key(|v| v.abs()), Some(&0));
+        assert_eq!(sut.max_by_key(|v| v.abs()), Some(&-500));
+
+        // odd length, and ties are resolved the same as the iterator methods
+        let sut: Vector<(u8, usize)> = (0..7).map(|i| ((i % 2) as u8, i)).collect();
+        let (min, max) = sut.min_max().unwrap();
+        assert_eq!(min, &(0, 0));
+        assert_eq!(max, &(1, 5));
+        let sut: Vector<KeyOnly> = [(1, 'a'), (0, 'b'), (1, 'c'), (0, 'd'), (1, 'e')]
+            .map(KeyOnly)
+            .into_iter()
+            .collect();
+        let (min, max) = sut.min_max().unwrap();
+        assert_eq!(min.0, sut.iter().min().unwrap().0);
+        assert_eq!(max.0, sut.iter().max().unwrap().0);
+
+        let single: Vector<i64> = [7].into_iter().collect();
+        assert_eq!(single.min_max(), Some((&7, &7)));
+        let empty: Vector<i64> = Vector::new();
+        assert_eq!(empty.min_max(), None);
+        assert_eq!(empty.min_by_key(|v| *v), None);
+    }
+
+    #[test]
+    fn test_vector_batch() {
+        let mut sut: Vector<usize> = (0..100).collect();
+        let mut reference: Vec<usize> = (0..100).collect();
+        let len = sut.batch(|batch| {
+            for value in 0..5000 {
+                let index = value % (batch.len() + 1);
+                batch.insert(index, value);
+                reference.insert(index, value);
+            }
+            for value in 0..1000 {
+                let index = (value * 7) % batch.len();
+                assert_eq!(batch.remove(index), reference.remove(index));
+            }
+            batch.push(1);
+            reference.push(1);
+            // blocks were added rather than the vector being expanded
+            assert_eq!(batch.vector.block_exponent(), 4);
+            batch.len()
+        });
+        assert_eq!(len, 4101);
+        assert!(sut.iter().eq(reference.iter()));
+        assert_eq!(sut.block_exponent(), 7);
+        assert_eq!(sut.min_block_fill(), Some(1.0));
+
+        // removing most of the elements rebuilds with smaller blocks
+        sut.batch(|batch| {
+            while batch.len() > 10 {
+                batch.pop();
+            }
+            assert!(batch.get(10).is_none());
+            *batch.get_mut(0).unwrap() = 0;
+            assert_eq!(batch.vector.block_exponent(), 7);
+        });
+        assert_eq!(sut.block_exponent(), 2);
+        assert_eq!(sut.len(), 10);
+        assert_eq!(sut[0], 0);
+    }
+
+    #[test]
+    fn test_vector_batch_unchanged() {
+        let mut sut: Vector<usize> = (0..16).collect();
+        assert_eq!(sut.block_exponent(), 2);
+        sut.batch(|_| ());
+        assert_eq!(sut.block_exponent(), 2);
+        sut.batch(|batch| {
+            batch.push(16);
+            batch.pop();
+        });
+        assert_eq!(sut.block_exponent(), 2);
+        assert!(sut.iter().copied().eq(0..16));
+
+        // neither expanding nor compressing within the limits
+        let mut sut: Vector<usize> = (0..100).collect();
+        assert_eq!(sut.block_exponent(), 4);
+        sut.batch(|batch| {
+            while batch.len() > 40 {
+                batch.pop();
+            }
+        });
+        assert_eq!(sut.block_exponent(), 4);
+        assert!(sut.iter().copied().eq(0..40));
+    }
+
+    /// Applies the edit script produced by `diff()` to a copy of the elements.
+    fn apply_script(source: &Vector<char>, edits: &[EditOp<char>]) -> Vec<char> {
+        let mut result: Vec<char> = source.iter().copied().collect();
+        for edit in edits {
+            match edit {
+                EditOp::Keep => (),
+                EditOp::Insert(index, value) => result.insert(*index, *value),
+                EditOp::Remove(index) => {
+                    result.remove(*index);
+                }
+            }
+        }
+        result
+    }
+
+    #[test]
+    fn test_vector_diff() {
+        let a: Vector<char> = "abcd".chars().collect();
+        // insertion
+        let b: Vector<char> = "abxcd".chars().collect();
+        let edits = a.diff(&b);
+        assert_eq!(
+            edits,
+            vec![
+                EditOp::Keep,
+                EditOp::Keep,
+                EditOp::Insert(2, 'x'),
+                EditOp::Keep,
+                EditOp::Keep
+            ]
+        );
+        assert!(b.iter().eq(apply_script(&a, &edits).iter()));
+        // deletion
+        let b: Vector<char> = "acd".chars().collect();
+        let edits = a.diff(&b);
+        assert_eq!(
+            edits,
+            vec![EditOp::Keep, EditOp::Remove(1), EditOp::Keep, EditOp::Keep]
+        );
+        assert!(b.iter().eq(apply_script(&a, &edits).iter()));
+        // substitution
+        let b: Vector<char> = "abyd".chars().collect();
+        let edits = a.diff(&b);
+        assert_eq!(edits.len(), 5);
+        assert_eq!(edits.iter().filter(|e| **e == EditOp::Keep).count(), 3);
+        assert!(b.iter().eq(apply_script(&a, &edits).iter()));
+
+        // larger inputs spanning many blocks
+        let a: Vector<char> = "the quick brown fox jumps over the lazy dog"
+            .chars()
+            .collect();
+        let b: Vector<char> = "a quick brown cat leaps over lazy dogs".chars().collect();
+        let edits = a.diff(&b);
+        assert!(b.iter().eq(apply_script(&a, &edits).iter()));
+        assert!(a.diff(&a).iter().all(|e| *e == EditOp::Keep));
+        let empty: Vector<char> = Vector::new();
+        assert_eq!(empty.diff(&a).len(), a.len());
+        assert!(empty.iter().eq(apply_script(&a, &a.diff(&empty)).iter()));
+    }
+
+    #[test]
+    fn test_vector_apply_edits() {
+        // grow the vector across several blocks
+        let mut a: Vector<usize> = (0..10).collect();
+        let b: Vector<usize> = (0..100).filter(|v| v % 3 != 1).collect();
+        let edits = a.diff(&b);
+        a.apply_edits(&edits).unwrap();
+        assert!(a.iter().eq(b.iter()));
+
+        // shrink the vector
+        let mut a: Vector<usize> = (0..200).collect();
+        let b: Vector<usize> = (0..200).filter(|v| v % 7 == 0).collect();
+        let edits = a.diff(&b);
+        a.apply_edits(&edits).unwrap();
+        assert!(a.iter().eq(b.iter()));
+        assert_eq!(a.len(), 29);
+
+        // inconsistent scripts leave the vector unchanged
+        let mut a: Vector<usize> = (0..3).collect();
+        let result = a.apply_edits(&[EditOp::Keep, EditOp::Remove(0)]);
+        assert_eq!(
+            result,
+            Err(EditError::IndexMismatch {
+                op: 1,
+                expected: 1,
+                found: 0
+            })
+        );
+        let result = a.apply_edits(&[EditOp::Remove(0), EditOp::Keep, EditOp::Keep, EditOp::Keep]);
+        assert_eq!(result, Err(EditError::OutOfBounds { op: 3 }));
+        let result = a.apply_edits(&[EditOp::Insert(4, 9)]);
+        assert!(result.is_err());
+        assert!(a.iter().eq([0, 1, 2].iter()));
+        a.apply_edits(&[]).unwrap();
+        assert_eq!(a.len(), 3);
+    }
+
+    #[test]
+    fn test_vector_copy_to_slice() {
+        let mut sut: Vector<u8> = Vector::new();
+        for value in 0..200 {
+            sut.push(value);
+        }
+        // rotate the blocks so the runs wrap around their buffers
+        for value in 0..50 {
+            sut.insert(0, value);
+            sut.remove(100);
+        }
+        let mut buffer = [0u8; 40];
+        sut.copy_to_slice(5, &mut buffer);
+        for (offset, value) in buffer.iter().enumerate() {
+            assert_eq!(value, &sut[5 + offset]);
+        }
+        let mut buffer = [0u8; 200];
+        sut.copy_to_slice(0, &mut buffer);
+        assert!(buffer.iter().eq(sut.iter()));
+        sut.copy_to_slice(200, &mut []);
+    }
+
+    #[test]
+    #[should_panic(expected = "range 190..210 out of bounds for length 200")]
+    fn test_vector_copy_to_slice_panic() {
+        let sut: Vector<u8> = (0..200).collect();
+        let mut buffer = [0u8; 20];
+        sut.copy_to_slice(190, &mut buffer);
+    }
+
+    #[test]
+    fn test_vector_get_by() {
+        struct RowId(usize);
+
+        impl From<RowId> for usize {
+            fn from(value: RowId) -> Self {
+                value.0
+            }
+        }
+
+        let mut sut: Vector<usize> = (0..10).map(|v| v * 10).collect();
+        assert_eq!(sut.get_by(RowId(3)), sut.get(3));
+        assert_eq!(sut.get_by(RowId(3)), Some(&30));
+        assert_eq!(sut.get_by(RowId(10)), None);
+        *sut.get_mut_by(RowId(4)).unwrap() = 99;
+        assert_eq!(sut[4], 99);
+        assert!(sut.get_mut_by(RowId(10)).is_none());
+        assert_eq!(sut.get_by(2usize), Some(&20));
+    }
+
+    #[test]
+    fn test_vector_truncate_at() {
+        // sentinel in the middle
+        let mut sut: Vector<usize> = (0..5000).collect();
+        assert!(sut.truncate_at(|v| *v == 1234));
+        assert_eq!(sut.len(), 1234);
+        assert!(sut.iter().eq((0..1234).collect::<Vec<_>>().iter()));
+        assert!(sut.capacity() < 2048);
+        sut.push(1234);
+        assert_eq!(sut[1234], 1234);
+
+        // match on the first element
+        let mut sut: Vector<String> = (0..100).map(|v| v.to_string()).collect();
+        assert!(sut.truncate_at(|v| v == "0"));
+        assert!(sut.is_empty());
+        assert_eq!(sut.capacity(), 0);
+        sut.push("a".to_owned());
+        assert_eq!(sut.len(), 1);
+
+        // no match
+        let mut sut: Vector<usize> = (0..100).collect();
+        assert!(!sut.truncate_at(|v| *v > 100));
+        assert_eq!(sut.len(), 100);
+        assert!(sut.iter().eq((0..100).collect::<Vec<_>>().iter()));
+    }
+
+    #[test]
+    fn test_vector_tier_info() {
+        let sut: Vector<usize> = Vector::new();
+        let info = sut.tier_info();
+        assert_eq!(info.k, 2);
+        assert_eq!(info.block_size, 4);
+        assert_eq!(info.block_count, 0);
+        assert_eq!(info.ideal_block_count, 0);
+        assert_eq!(info.fill_ratio, 0.0);
+
+        let mut sut: Vector<usize> = (0..100).collect();
+        let info = sut.tier_info();
+        assert_eq!(info.k, 4);
+        assert_eq!(info.block_size, 16);
+        assert_eq!(info.block_count, 7);
+        assert_eq!(info.ideal_block_count, 7);
+        assert_eq!(info.fill_ratio, 100.0 / 112.0);
+
+        // spare blocks leave the layout far from ideal
+        let _ = unsafe { sut.reserve_uninit(100) };
+        let info = sut.tier_info();
+        assert_eq!(info.block_count, 13);
+        assert_eq!(info.ideal_block_count, 7);
+
+        // rebuilding the vector releases the spare blocks
+        sut.reshape_to_exponent(4).unwrap();
+        let info = sut.tier_info();
+        assert_eq!(info.block_count, 7);
+        assert_eq!(info.ideal_block_count, 7);
+        let _ = unsafe { sut.reserve_uninit(50) };
+        assert_eq!(sut.tier_info().block_count, 10);
+        sut.shrink_to_fit();
+        let info = sut.tier_info();
+        assert_eq!(info.block_count, 7);
+        assert_eq!(info.ideal_block_count, 7);
+    }
+
+    #[test]
+    fn test_vector_concat_vectors() {
+        let sizes = [0, 10, 0, 1000, 3, 0, 250, 1];
+        let mut inputs: Vec<Vector<usize>> = Vec::new();
+        let mut start = 0;
+        for size in sizes {
+            inputs.push((start..start + size).collect());
+            start += size;
+        }
+        let sut = Vector::concat_vectors(inputs);
+        assert_eq!(sut.len(), start);
+        for (index, value) in sut.iter().enumerate() {
+            assert_eq!(*value, index);
+        }
+
+        // blocks are adopted when the layout allows
+        let a: Vector<usize> = (0..8).collect();
+        let b: Vector<usize> = (8..14).collect();
+        let mut sut = Vector::concat_vectors([a, b]);
+        let info = sut.tier_info();
+        assert_eq!(info.k, 2);
+        assert_eq!(info.block_count, 4);
+        sut.push(14);
+        sut.push(15);
+        sut.push(16);
+        assert!(sut.iter().eq((0..17).collect::<Vec<_>>().iter()));
+
+        let empty: Vec<Vector<usize>> = vec![Vector::new(), Vector::new()];
+        assert!(Vector::concat_vectors(empty).is_empty());
+    }
+
+    #[test]
+    fn test_vector_with_capacity() {
+        let sut: Vector<usize> = Vector::with_capacity(0);
+        assert_eq!(sut.capacity(), 0);
+        assert_eq!(sut.tier_info(), Vector::<usize>::new().tier_info());
+
+        for n in [1, 16, 17, 1000, 4096, 5000] {
+            let mut sut: Vector<usize> = Vector::with_capacity(n);
+            assert!(sut.capacity() >= n);
+            assert!(sut.is_empty());
+            let before = sut.block_exponent();
+            for value in 0..n {
+                sut.push(value);
+            }
+            assert_eq!(sut.block_exponent(), before);
+            assert_eq!(sut.len(), n);
+            assert_eq!(sut[n - 1], n - 1);
+        }
+        let sut: Vector<usize> = Vector::with_capacity(1000);
+        assert_eq!(sut.block_exponent(), 5);
+        assert_eq!(sut.capacity(), 1024);
+    }
+
+    #[test]
+    fn test_vector_reversed() {
+        for len in [0, 1, 999, 1000] {
+            let mut sut: Vector<usize> = (0..len).collect();
+            // rotate some blocks so that their elements wrap around
+            if len > 10 {
+                sut.insert(0, 0);
+                sut.remove(0);
+            }
+            let reversed = sut.reversed();
+            assert_eq!(reversed.len(), len);
+            assert!(
+                reversed
+                    .iter()
+                    .eq((0..len).rev().collect::<Vec<_>>().iter())
+            );
+            assert!(sut.iter().eq((0..len).collect::<Vec<_>>().iter()));
+        }
+        let sut: Vector<String> = (0..99).map(|v| v.to_string()).collect();
+        let mut reversed = sut.reversed();
+        assert_eq!(reversed[0], "98");
+        assert_eq!(reversed[98], "0");
+        reversed.push("last".to_owned());
+        reversed.remove(0);
+        assert_eq!(reversed.len(), 99);
+        assert_eq!(reversed[98], "last");
+    }
+
+    #[test]
+    fn test_vector_clear_keep_capacity() {
+        let mut sut: Vector<String> = (0..1000).map(|v| v.to_string()).collect();
+        let capacity = sut.capacity();
+        let info = sut.tier_info();
+        sut.clear_keep_capacity();
+        assert!(sut.is_empty());
+        assert_eq!(sut.capacity(), capacity);
+        assert_eq!(sut.tier_info().block_count, info.block_count);
+        assert_eq!(sut.tier_info().k, info.k);
+        for value in 0..1000 {
+            sut.push(value.to_string());
+        }
+        assert_eq!(sut.capacity(), capacity);
+        assert_eq!(sut[999], "999");
+
+        sut.clear();
+        assert!(sut.is_empty());
+        assert_eq!(sut.capacity(), 0);
+        assert_eq!(sut.tier_info(), Vector::<String>::new().tier_info());
+        sut.push("a".to_owned());
+        assert_eq!(sut.capacity(), 4);
+    }
+
+    #[cfg(feature = "metrics")]
+    #[test]
+    fn test_vector_clear_keep_capacity_no_alloc() {
+        let mut sut: Vector<usize> = (0..1000).collect();
+        let stats = sut.alloc_stats();
+        sut.clear_keep_capacity();
+        assert_eq!(sut.alloc_stats(), stats);
+        for value in 0..1000 {
+            sut.push(value);
+        }
+        assert_eq!(sut.alloc_stats(), stats);
+        let blocks = sut.tier_info().block_count;
+        sut.clear();
+        assert_eq!(sut.alloc_stats().deallocs, stats.deallocs + blocks);
+    }
+
+    #[cfg(feature = "metrics")]
+    #[test]
+    fn test_vector_clear_keep_capacity_compress() {
+        let mut sut: Vector<usize> = (0..1000).collect();
+        assert_eq!(sut.tier_info().k, 5);
+        assert_eq!(sut.tier_info().block_count, 32);
+        sut.clear_keep_capacity();
+        let stats = sut.alloc_stats();
+        for value in 0..5 {
+            sut.push(value);
+        }
+        assert_eq!(sut.alloc_stats(), stats);
+        // compressing splits only the block in use and releases the others
+        assert_eq!(sut.remove(0), 0);
+        assert_eq!(sut.tier_info().k, 4);
+        assert_eq!(sut.tier_info().block_count, 2);
+        assert_eq!(sut.alloc_stats().allocs, stats.allocs + 2);
+        assert_eq!(sut.alloc_stats().deallocs, stats.deallocs + 32);
+        assert!(sut.iter().copied().eq(1..5));
+    }
+
+    #[test]
+    fn test_vector_reserve() {
+        let mut sut: Vector<usize> = Vector::new();
+        sut.reserve(0);
+        assert_eq!(sut.capacity(), 0);
+        sut.reserve(1000);
+        assert!(sut.capacity() >= 1000);
+        let before = sut.block_exponent();
+        for value in 0..1000 {
+            sut.push(value);
+        }
+        assert_eq!(sut.block_exponent(), before);
+
+        // reserving more on a populated vector expands it up front
+        sut.reserve(5000);
+        let capacity = sut.capacity();
+        assert!(capacity >= 6000);
+        let before = sut.block_exponent();
+        for value in 1000..6000 {
+            sut.push(value);
+        }
+        assert_eq!(sut.block_exponent(), before);
+        assert_eq!(sut.capacity(), capacity);
+        sut.reserve(0);
+        assert_eq!(sut.capacity(), capacity);
+        for value in 0..6000 {
+            assert_eq!(sut[value], value);
+        }
+    }
+
+    #[test]
+    fn test_vector_fingerprint() {
+        let mut sut: Vector<usize> = (0..100).collect();
+        let original = sut.fingerprint();
+        // expand and then compress back to the same contents
+        for value in 100..5000 {
+            sut.push(value);
+        }
+        assert_ne!(sut.fingerprint(), original);
+        while sut.len() > 100 {
+            sut.pop();
+        }
+        assert_eq!(sut.fingerprint(), original);
+
+        // independently built vectors with different layouts
+        let mut other: Vector<usize> = Vector::new();
+        for value in (0..100).rev() {
+            other.insert(0, value);
+        }
+        assert_eq!(other.fingerprint(), original);
+
+        other[50] = 51;
+        assert_ne!(other.fingerprint(), original);
+        other[50] = 50;
+        assert_eq!(other.fingerprint(), original);
+        other.push(100);
+        assert_ne!(other.fingerprint(), original);
+        assert_ne!(
+            Vector::<usize>::new().fingerprint(),
+            Vector::<usize>::from_iter([0]).fingerprint()
+        );
+    }
+
+    #[test]
+    fn test_vector_fingerprint_fixed() {
+        // the FNV-1a hash of no bytes is the offset basis
+        assert_eq!(Fnv1aHasher::default().finish(), 0xcbf29ce484222325);
+        let mut hasher = Fnv1aHasher::default();
+        hasher.write(b"a");
+        assert_eq!(hasher.finish(), 0xaf63dc4c8601ec8c);
+        let mut hasher = Fnv1aHasher::default();
+        hasher.write(b"foobar");
+        assert_eq!(hasher.finish(), 0x85944171f73967e8);
+
+        // same as hashing the length and the bytes of the elements
+        let sut: Vector<u8> = Vector::from([1, 2, 3]);
+        let mut hasher = Fnv1aHasher::default();
+        hasher.write(&3usize.to_ne_bytes());
+        hasher.write(&[1, 2, 3]);
+        assert_eq!(sut.fingerprint(), hasher.finish());
+    }
+
+    #[test]
+    fn test_vector_shrink_to_fit() {
+        let mut sut: Vector<usize> = Vector::with_capacity(10_000);
+        sut.extend_from_slice(&(0..100).collect::<Vec<_>>());
+        assert_eq!(sut.block_exponent(), 7);
+        sut.shrink_to_fit();
+        // 100 elements fit within 16 blocks of 16
+        assert_eq!(sut.block_exponent(), 4);
+        assert_eq!(sut.capacity(), 112);
+        assert!(sut.iter().eq((0..100).collect::<Vec<_>>().iter()));
+        sut.push(100);
+        assert_eq!(sut[100], 100);
+
+        // never shrink below blocks of 4
+        let mut sut: Vector<usize> = (0..3).collect();
+        sut.reserve(1000);
+        sut.shrink_to_fit();
+        assert_eq!(sut.block_exponent(), 2);
+        assert_eq!(sut.capacity(), 4);
+        let mut sut: Vector<usize> = Vector::with_capacity(1000);
+        sut.shrink_to_fit();
+        assert_eq!(sut.capacity(), 0);
+        assert_eq!(sut.block_exponent(), 2);
+    }
+
+    #[test]
+    fn test_vector_iter_mut() {
+        let mut sut: Vector<usize> = (0..100_000).collect();
+        // rotate some blocks so that their elements wrap around
+        sut.insert(0, 0);
+        sut.remove(0);
+        for x in sut.iter_mut() {
+            *x *= 2;
+        }
+        assert_eq!(sut.len(), 100_000);
+        for (index, value) in sut.iter().enumerate() {
+            assert_eq!(*value, index * 2);
+        }
+
+        let mut sut: Vector<String> = Vector::with_capacity(100);
+        assert!(sut.iter_mut().next().is_none());
+        sut.push("a".to_owned());
+        sut.push("b".to_owned());
+        for value in sut.iter_mut() {
+            value.push('!');
+        }
+        assert_eq!(sut[0], "a!");
+        assert_eq!(sut[1], "b!");
+        assert_eq!(sut.iter_mut().count(), 2);
+    }
+
+    #[test]
+    fn test_vector_block_of() {
+        let mut sut: Vector<usize> = (0..1000).collect();
+        sut.insert(0, 0);
+        sut.remove(0);
+        for index in [0, 31, 32, 500, 999] {
+            let (block, offset) = sut.block_of(index).unwrap();
+            assert_eq!(block.capacity(), 32);
+            assert_eq!(offset, index % 32);
+            assert_eq!(block.get(offset), sut.get(index));
+        }
+        let (block, _) = sut.block_of(999).unwrap();
+        assert_eq!(block.len(), 8);
+        assert!(sut.block_of(1000).is_none());
+        assert!(Vector::<usize>::new().block_of(0).is_none());
+    }
+
+    #[test]
+    fn test_vector_drain() {
+        let mut sut: Vector<usize> = (0..10_000).collect();
+        let drained: Vec<usize> = sut.drain(4000..6000).collect();
+        assert_eq!(drained.len(), 2000);
+        assert!(drained.iter().eq((4000..6000).collect::<Vec<_>>().iter()));
+        assert_eq!(sut.len(), 8000);
+        assert!(
+            sut.iter()
+                .eq((0..4000).chain(6000..10_000).collect::<Vec<_>>().iter())
+        );
+
+        // dropping early still removes the entire range
+        let mut sut: Vector<String> = (0..1000).map(|v| v.to_string()).collect();
+        let mut drain = sut.drain(10..=500);
+        assert_eq!(drain.next(), Some("10".to_owned()));
+        assert_eq!(drain.next_back(), Some("500".to_owned()));
+        drop(drain);
+        assert_eq!(sut.len(), 509);
+        assert_eq!(sut[9], "9");
+        assert_eq!(sut[10], "501");
+
+        assert_eq!(sut.drain(..).count(), 509);
+        assert!(sut.is_empty());
+        assert_eq!(sut.drain(..).count(), 0);
+    }
+
+    #[test]
+    fn test_vector_drain_compress() {
+        let mut sut: Vector<usize> = (0..10_000).collect();
+        assert_eq!(sut.block_exponent(), 7);
+        let mut drain = sut.drain(100..9900);
+        assert_eq!(drain.next(), Some(100));
+        assert_eq!(drain.size_hint(), (9799, Some(9799)));
+        drop(drain);
+        // compressed twice to the size that suits the remaining elements
+        assert_eq!(sut.block_exponent(), 5);
+        assert!(sut.iter().copied().eq((0..100).chain(9900..10_000)));
+        let used = sut.len().div_ceil(sut.l);
+        assert_eq!(sut.tier_info().block_count, used);
+        assert!(sut.index[..used - 1].iter().all(|b| b.is_full()));
+
+        // ranges at either end, within and across blocks
+        for (start, end) in [(0, 0), (0, 5), (3, 7), (0, 200), (50, 1000), (999, 1000)] {
+            let mut sut: Vector<usize> = (0..1000).collect();
+            assert!(sut.drain(start..end).eq(start..end));
+            assert!(sut.iter().copied().eq((0..start).chain(end..1000)));
+            let used = sut.len().div_ceil(sut.l);
+            assert_eq!(sut.tier_info().block_count, used);
+            assert!(sut.index[..used - 1].iter().all(|b| b.is_full()));
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "drain end (is 11) should be <= len (is 10)")]
+    fn test_vector_drain_panics() {
+        let mut sut: Vector<usize> = (0..10).collect();
+        sut.drain(5..11);
+    }
+
+    #[test]
+    fn test_vector_trim_end_value() {
+        let mut sut: Vector<u8> = Vector::new();
+        for value in 1..=100 {
+            sut.push(value);
+        }
+        for _ in 0..300 {
+            sut.push(0);
+        }
+        assert_eq!(sut.trim_end_value(&0), 300);
+        assert_eq!(sut.len(), 100);
+        assert_eq!(sut[99], 100);
+        assert_eq!(sut.trim_end_value(&0), 0);
+        assert_eq!(sut.len(), 100);
+
+        let mut sut: Vector<u8> = Vector::with_len(500);
+        assert_eq!(sut.trim_end_value(&0), 500);
+        assert!(sut.is_empty());
+        assert_eq!(sut.trim_end_value(&0), 0);
+    }
+
+    #[test]
+    fn test_vector_dot() {
+        let a: Vector<i64> = Vector::from_iter([1, 2, 3, 4, 5]);
+        let b: Vector<i64> = Vector::from_iter([6, 7, 8, 9, 10]);
+        assert_eq!(a.dot(&b), 6 + 14 + 24 + 36 + 50);
+        let empty: Vector<i64> = Vector::new();
+        assert_eq!(empty.dot(&empty), 0);
+
+        // span many blocks and groups with differing layouts
+        let a: Vector<u64> = (0..1000).collect();
+        let mut b: Vector<u64> = Vector::new();
+        for value in (0..1000).rev() {
+            b.insert(0, value);
+        }
+        let expected: u64 = (0..1000u64).map(|v| v * v).sum();
+        assert_eq!(a.dot(&b), expected);
+        let c: Vector<f64> = (0..1000).map(|v| v as f64 * 0.5).collect();
+        let expected: f64 = (0..1000).map(|v| v as f64 * 0.25 * v as f64).sum();
+        assert!((c.dot(&c) - expected).abs() < 1e-6);
+    }
+
+    #[test]
+    #[should_panic(expected = "vector lengths (are 3 and 2) should be equal")]
+    fn test_vector_dot_panics() {
+        let a: Vector<i32> = Vector::from_iter([1, 2, 3]);
+        let b: Vector<i32> = Vector::from_iter([1, 2]);
+        a.dot(&b);
+    }
+
+    #[test]
+    fn test_vector_extend() {
+        let mut sut: Vector<usize> = Vector::new();
+        sut.extend(0..10_000);
+        // expanding would have released the smaller blocks
+        #[cfg(feature = "metrics")]
+        assert_eq!(sut.alloc_stats().deallocs, 0);
+        let mut expected: Vector<usize> = Vector::new();
+        for value in 0..10_000 {
+            expected.push(value);
+        }
+        assert!(sut.structurally_eq(&expected));
+        assert_eq!(sut.tier_info(), expected.tier_info());
+
+        // iterators without a useful size hint
+        sut.extend((10_000..11_000).filter(|v| v % 2 == 0));
+        assert_eq!(sut.len(), 10_500);
+        assert_eq!(sut[10_499], 10_998);
+
+        let mut sut: Vector<u8> = Vector::new();
+        let values = [1u8, 2, 3];
+        sut.extend(values.iter());
+        sut.extend(&values);
+        assert!(sut.iter().eq([1, 2, 3, 1, 2, 3].iter()));
+    }
+
+    #[test]
+    fn test_vector_retain() {
+        let mut sut: Vector<usize> = (0..1000).collect();
+        sut.retain(|v| v % 2 == 0);
+        assert_eq!(sut.len(), 500);
+        assert!(
+            sut.iter()
+                .eq((0..1000).step_by(2).collect::<Vec<_>>().iter())
+        );
+
+        let mut sut: Vector<String> = (0..100).map(|v| v.to_string()).collect();
+        sut.retain(|v| v.len() == 1);
+        assert_eq!(sut.len(), 10);
+        assert_eq!(sut[9], "9");
+        sut.retain(|_| true);
+        assert_eq!(sut.len(), 10);
+        sut.retain(|_| false);
+        assert!(sut.is_empty());
+        assert_eq!(sut.capacity(), 0);
+        sut.push("a".to_owned());
+        assert_eq!(sut.len(), 1);
+    }
+
+    #[test]
+    fn test_ring_vector() {
+        let mut sut: RingVector<usize> = RingVector::with_capacity(100);
+        assert!(sut.is_empty());
+        assert_eq!(sut.capacity(), 100);
+        for value in 0..100 {
+            assert_eq!(sut.push(value), None);
+        }
+        assert_eq!(sut.len(), 100);
+        for value in 100..250 {
+            assert_eq!(sut.push(value), Some(value - 100));
+            assert!(sut.recent(3).eq([value - 2, value - 1, value].iter()));
+        }
+        assert_eq!(sut.len(), 100);
+        assert!(sut.recent(100).eq((150..250).collect::<Vec<_>>().iter()));
+        assert!(sut.recent(500).eq((150..250).collect::<Vec<_>>().iter()));
+        assert_eq!(sut.recent(0).count(), 0);
+
+        let mut sut: RingVector<&str> = RingVector::with_capacity(0);
+        assert_eq!(sut.push("a"), Some("a"));
+        assert!(sut.is_empty());
+    }
+
+    #[test]
+    fn test_vector_truncate() {
+        let mut sut: Vector<String> = (0..1000).map(|v| v.to_string()).collect();
+        sut.truncate(1000);
+        assert_eq!(sut.len(), 1000);
+        sut.truncate(10);
+        assert_eq!(sut.len(), 10);
+        assert!(sut.capacity() < 64);
+        assert_eq!(sut[9], "9");
+        sut.push("10".to_owned());
+        assert_eq!(sut[10], "10");
+
+        let dropped = std::rc::Rc::new(std::cell::Cell::new(0));
+        let mut sut: Vector<DropCounter> = Vector::new();
+        for _ in 0..1000 {
+            sut.push(DropCounter(dropped.clone()));
+        }
+        sut.truncate(10);
+        assert_eq!(dropped.get(), 990);
+        drop(sut);
+        assert_eq!(dropped.get(), 1000);
+
+        let mut sut: Vector<u32> = (0..1000).collect();
+        sut.truncate(500);
+        assert!(sut.iter().eq((0..500).collect::<Vec<_>>().iter()));
+        sut.truncate(0);
+        assert!(sut.is_empty());
+        assert_eq!(sut.capacity(), 0);
+    }
+
+    #[test]
+    fn test_vector_gather() {
+        let sut: Vector<String> = (0..1000).map(|v| v.to_string()).collect();
+        let gathered = sut.gather(&[999, 0, 512, 31, 32, 512, 0]);
+        let expected = ["999", "0", "512", "31", "32", "512", "0"];
+        assert_eq!(gathered.len(), expected.len());
+        assert!(gathered.iter().eq(expected.iter()));
+        assert_eq!(sut.len(), 1000);
+        assert!(sut.gather(&[]).is_empty());
+    }
+
+    #[test]
+    #[should_panic(expected = "index out of bounds")]
+    fn test_vector_gather_panics() {
+        let sut: Vector<usize> = (0..10).collect();
+        sut.gather(&[1, 10]);
+    }
+
+    #[test]
+    fn test_vector_scatter() {
+        let mut sut: Vector<usize> = (0..1000).collect();
+        sut.scatter(&[999, 0, 500, 0], vec![1, 2, 3, 4]);
+        assert_eq!(sut[999], 1);
+        assert_eq!(sut[0], 4);
+        assert_eq!(sut[500], 3);
+        assert_eq!(sut[1], 1);
+        assert_eq!(sut.len(), 1000);
+
+        let dropped = std::rc::Rc::new(std::cell::Cell::new(0));
+        let mut sut: Vector<DropCounter> = Vector::new();
+        for _ in 0..100 {
+            sut.push(DropCounter(dropped.clone()));
+        }
+        let values: Vec<DropCounter> = (0..3).map(|_| DropCounter(dropped.clone())).collect();
+        sut.scatter(&[10, 90, 10], values);
+        assert_eq!(dropped.get(), 3);
+        drop(sut);
+        assert_eq!(dropped.get(), 100 + 3);
+    }
+
+    #[test]
+    #[should_panic(expected = "scatter index (is 10) should be < len (is 10)")]
+    fn test_vector_scatter_out_of_bounds() {
+        let mut sut: Vector<usize> = (0..10).collect();
+        sut.scatter(&[1, 10], vec![1, 2]);
+    }
+
+    #[test]
+    #[should_panic(expected = "indices length (is 1) should be == values length (is 2)")]
+    fn test_vector_scatter_length_mismatch() {
+        let mut sut: Vector<usize> = (0..10).collect();
+        sut.scatter(&[1], vec![1, 2]);
+    }
+
+    #[test]
+    fn test_vector_clone() {
+        let mut sut: Vector<String> = (0..1000).map(|v| v.to_string()).collect();
+        sut.insert(0, "first".to_owned());
+        let cloned = sut.clone();
+        assert!(cloned.structurally_eq(&sut));
+        assert_eq!(cloned.tier_info(), sut.tier_info());
+        sut[0].push('!');
+        sut.remove(500);
+        sut.push("last".to_owned());
+        assert_eq!(cloned.len(), 1001);
+        assert_eq!(cloned[0], "first");
+        assert_eq!(cloned[500], "499");
+        assert_eq!(cloned[1000], "999");
+        assert_eq!(sut[0], "first!");
+
+        let empty: Vector<String> = Vector::new();
+        let mut cloned = empty.clone();
+        assert!(cloned.is_empty());
+        cloned.push("a".to_owned());
+        assert_eq!(cloned.len(), 1);
     }
 
-    /// Returns a mutable reference to an element.
-    pub fn get_mut(&mut self, index: usize) -> Option<&mut T> {
-        if index < self.count {
-            let idx = self.physical_add(index);
-            unsafe { (self.buffer.add(idx)).as_mut() }
-        } else {
-            None
+    #[test]
+    fn test_vector_debug() {
+        let mut sut: Vector<usize> = Vector::new();
+        assert_eq!(format!("{:?}", sut), "[]");
+        for value in (0..10).rev() {
+            sut.insert(0, value);
         }
+        assert_eq!(format!("{:?}", sut), "[0, 1, 2, 3, 4, 5, 6, 7, 8, 9]");
+        let sut: Vector<&str> = Vector::from_iter(["a", "b"]);
+        assert_eq!(format!("{:?}", sut), r#"["a", "b"]"#);
+        assert_eq!(format!("{}", sut), "Vector(k: 2, count: 2, dope: 1)");
     }
 
-    /// Clears the cyclic array, removing and dropping all values.
-    pub fn clear(&mut self) {
-        use std::ptr::{drop_in_place, slice_from_raw_parts_mut};
-
-        if self.count > 0 && std::mem::needs_drop::<T>() {
-            let first_slot = self.physical_add(0);
-            let last_slot = self.physical_add(self.count);
-            if first_slot < last_slot {
-                // elements are in one contiguous block
-                unsafe {
-                    drop_in_place(slice_from_raw_parts_mut(
-                        self.buffer.add(first_slot),
-                        last_slot - first_slot,
-                    ));
-                }
-            } else {
-                // elements wrap around the end of the buffer
-                unsafe {
-                    drop_in_place(slice_from_raw_parts_mut(
-                        self.buffer.add(first_slot),
-                        self.capacity - first_slot,
-                    ));
-                    // check if first and last are at the start of the array
-                    if first_slot != last_slot || first_slot != 0 {
-                        drop_in_place(slice_from_raw_parts_mut(self.buffer, last_slot));
-                    }
-                }
-            }
+    #[test]
+    fn test_vector_cloned_windows() {
+        let expected: Vec<usize> = (0..100).collect();
+        let sut: Vector<usize> = (0..100).collect();
+        for (size, step) in [(1, 1), (3, 1), (4, 2), (10, 10), (3, 7), (100, 1), (101, 1)] {
+            let actual: Vec<Vec<usize>> = sut.cloned_windows(size, step).collect();
+            let reference: Vec<Vec<usize>> = expected
+                .windows(size)
+                .step_by(step)
+                .map(|w| w.to_vec())
+                .collect();
+            assert_eq!(actual, reference, "size {size} step {step}");
         }
-        self.head = 0;
-        self.count = 0;
+        let empty: Vector<usize> = Vector::new();
+        assert_eq!(empty.cloned_windows(1, 1).count(), 0);
     }
 
-    /// Return the number of elements in the array.
-    pub fn len(&self) -> usize {
-        self.count
+    #[test]
+    #[should_panic(expected = "window size must be greater than zero")]
+    fn test_vector_cloned_windows_zero_size() {
+        let sut: Vector<usize> = (0..10).collect();
+        let _ = sut.cloned_windows(0, 1);
     }
 
-    /// Returns the total number of elements the cyclic array can hold.
-    pub fn capacity(&self) -> usize {
-        self.capacity
+    #[test]
+    #[should_panic(expected = "step must be greater than zero")]
+    fn test_vector_cloned_windows_zero_step() {
+        let sut: Vector<usize> = (0..10).collect();
+        let _ = sut.cloned_windows(1, 0);
     }
 
-    /// Returns true if the array has a length of 0.
-    pub fn is_empty(&self) -> bool {
-        self.count == 0
+    #[test]
+    fn test_vector_eq() {
+        let pushed: Vector<usize> = (0..1000).collect();
+        let mut inserted: Vector<usize> = Vector::new();
+        for value in (0..1000).rev() {
+            inserted.insert(0, value);
+        }
+        assert!(!pushed.structurally_eq(&inserted));
+        assert_eq!(pushed, inserted);
+
+        // same elements with a different block size
+        let mut reshaped = pushed.clone();
+        reshaped.reshape_to_exponent(8).unwrap();
+        assert_eq!(reshaped, pushed);
+
+        inserted[500] = 0;
+        assert_ne!(pushed, inserted);
+        let shorter: Vector<usize> = (0..999).collect();
+        assert_ne!(pushed, shorter);
+        assert_ne!(shorter, pushed);
+        assert_eq!(Vector::<usize>::new(), Vector::<usize>::new());
     }
 
-    /// Returns true if the array has a length equal to its capacity.
-    pub fn is_full(&self) -> bool {
-        self.count == self.capacity
+    #[test]
+    fn test_vector_mean_variance() {
+        let sut: Vector<u8> = Vector::from_iter([2, 4, 4, 4, 5, 5, 7, 9]);
+        assert!((sut.mean().unwrap() - 5.0).abs() < 1e-12);
+        assert!((sut.variance().unwrap() - 4.0).abs() < 1e-12);
+
+        let sut: Vector<f32> = Vector::from_iter([1.5, 2.5]);
+        assert!((sut.mean().unwrap() - 2.0).abs() < 1e-12);
+        assert!((sut.variance().unwrap() - 0.25).abs() < 1e-12);
+
+        // large offset that defeats the naive sum of squares
+        let sut: Vector<f64> = (0..1000).map(|v| 1e9 + (v % 2) as f64).collect();
+        assert!((sut.mean().unwrap() - (1e9 + 0.5)).abs() < 1e-6);
+        assert!((sut.variance().unwrap() - 0.25).abs() < 1e-6);
+
+        let empty: Vector<u32> = Vector::new();
+        assert_eq!(empty.mean(), None);
+        assert_eq!(empty.variance(), None);
     }
 
-    /// Perform a wrapping addition relative to the head of the array and
-    /// convert the logical offset to the physical offset within the array.
-    fn physical_add(&self, addend: usize) -> usize {
-        let logical_index = self.head.wrapping_add(addend);
-        if logical_index >= self.capacity {
-            logical_index - self.capacity
-        } else {
-            logical_index
+    #[test]
+    fn test_vector_hash() {
+        let pushed: Vector<usize> = (0..100).collect();
+        let mut inserted: Vector<usize> = Vector::new();
+        for value in (0..100).rev() {
+            inserted.insert(0, value);
         }
+        assert!(!pushed.structurally_eq(&inserted));
+        let mut set: HashSet<Vector<usize>> = HashSet::new();
+        set.insert(pushed);
+        set.insert(inserted);
+        assert_eq!(set.len(), 1);
+        set.insert((0..99).collect());
+        assert_eq!(set.len(), 2);
     }
 
-    /// Perform a wrapping subtraction relative to the head of the array and
-    /// convert the logical offset to the physical offset within the array.
-    fn physical_sub(&self, subtrahend: usize) -> usize {
-        let logical_index = self
-            .head
-            .wrapping_sub(subtrahend)
-            .wrapping_add(self.capacity);
-        if logical_index >= self.capacity {
-            logical_index - self.capacity
-        } else {
-            logical_index
-        }
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_vector_unique() {
+        let mut sut: Vector<usize> = Vector::from_iter([3, 1, 3, 2, 1, 4, 2, 3, 5]);
+        sut.unique();
+        assert!(sut.iter().eq([3, 1, 2, 4, 5].iter()));
+
+        let mut sut: Vector<usize> = (0..1000).map(|v| v % 7).collect();
+        sut.unique();
+        assert!(sut.iter().eq((0..7).collect::<Vec<_>>().iter()));
+        assert!(sut.capacity() < 16);
+
+        let mut sut: Vector<String> = (0..100).map(|v| v.to_string()).collect();
+        let expected = sut.clone();
+        sut.unique();
+        assert_eq!(sut, expected);
     }
-}
 
-impl<T> Default for CyclicArray<T> {
-    fn default() -> Self {
-        Self::new(0)
+    #[test]
+    fn test_vector_insert_sorted() {
+        let mut sut: Vector<usize> = Vector::new();
+        assert_eq!(sut.insert_sorted(5), 0);
+        assert_eq!(sut.insert_sorted(1), 0);
+        assert_eq!(sut.insert_sorted(9), 2);
+        assert_eq!(sut.insert_sorted(5), 2);
+        assert!(sut.iter().eq([1, 5, 5, 9].iter()));
+
+        // ties are placed after existing equal elements
+        assert_eq!(sut.insert_sorted(5), 3);
+        assert_eq!(sut.insert_sorted(1), 1);
+        assert_eq!(sut.insert_sorted(0), 0);
+        assert!(sut.iter().eq([0, 1, 1, 5, 5, 5, 9].iter()));
+
+        let mut values: Vec<usize> = (0..2000).map(|v| v % 500).collect();
+        for i in (1..values.len()).rev() {
+            values.swap(i, rand::random_range(0..=i));
+        }
+        let mut sut: Vector<usize> = Vector::new();
+        for value in values.iter() {
+            let index = sut.insert_sorted(*value);
+            assert_eq!(sut[index], *value);
+        }
+        values.sort();
+        assert!(sut.is_sorted());
+        assert!(sut.iter().eq(values.iter()));
     }
-}
 
-impl<T> fmt::Display for CyclicArray<T> {
-    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        write!(
-            f,
-            "CyclicArray(capacity: {}, head: {}, count: {})",
-            self.capacity, self.head, self.count,
-        )
+    #[test]
+    fn test_vector_ord() {
+        let inputs: Vec<Vec<i32>> = vec![
+            vec![3, 1],
+            vec![],
+            vec![1, 2, 3],
+            vec![1, 2],
+            vec![-1, 100],
+            vec![1, 2, 3, 0],
+            vec![3],
+        ];
+        let mut expected = inputs.clone();
+        expected.sort();
+        let mut sut: Vec<Vector<i32>> = inputs.into_iter().map(Vector::from_iter).collect();
+        sut.sort();
+        for (actual, expected) in sut.iter().zip(expected.iter()) {
+            assert!(actual.iter().eq(expected.iter()));
+        }
+        let a: Vector<f64> = Vector::from_iter([1.0, f64::NAN]);
+        let b: Vector<f64> = Vector::from_iter([1.0, 2.0]);
+        assert_eq!(a.partial_cmp(&b), None);
+        let c: Vector<f64> = Vector::from_iter([0.5, f64::NAN]);
+        assert!(c < b);
     }
-}
 
-impl<T> Index<usize> for CyclicArray<T> {
-    type Output = T;
+    #[test]
+    fn test_vector_first_last() {
+        let mut sut: Vector<usize> = Vector::new();
+        assert_eq!(sut.first(), None);
+        assert_eq!(sut.last(), None);
+        assert_eq!(sut.first_mut(), None);
+        assert_eq!(sut.last_mut(), None);
+        sut.push(7);
+        assert_eq!(sut.first(), Some(&7));
+        assert_eq!(sut.last(), Some(&7));
+        sut.extend(0..100);
+        assert_eq!(sut.first(), Some(&7));
+        assert_eq!(sut.last(), Some(&99));
+        *sut.first_mut().unwrap() = 1;
+        *sut.last_mut().unwrap() = 2;
+        assert_eq!(sut[0], 1);
+        assert_eq!(sut[100], 2);
+    }
 
-    fn index(&self, index: usize) -> &Self::Output {
-        let Some(item) = self.get(index) else {
-            panic!("index out of bounds: {}", index);
-        };
-        item
+    #[test]
+    fn test_vector_pack() {
+        // same block size with partial blocks in the middle
+        let mut inputs: Vec<Vector<usize>> = Vec::new();
+        let mut start = 0;
+        for size in [64, 90, 0, 96, 3] {
+            let mut input: Vector<usize> = Vector::with_capacity(256);
+            input.extend(start..start + size);
+            inputs.push(input);
+            start += size;
+        }
+        assert!(inputs.iter().all(|v| v.block_exponent() == 4));
+        let sut = Vector::pack(inputs);
+        assert_eq!(sut.block_exponent(), 4);
+        assert_eq!(sut.len(), start);
+        assert!(sut.iter().eq((0..start).collect::<Vec<_>>().iter()));
+        assert_eq!(sut.min_block_fill(), Some(1.0));
+
+        // combined size requires larger blocks
+        let inputs: Vec<Vector<usize>> =
+            (0..4).map(|i| (i * 200..(i + 1) * 200).collect()).collect();
+        let mut sut = Vector::pack(inputs);
+        assert_eq!(sut.block_exponent(), 5);
+        assert!(sut.iter().eq((0..800).collect::<Vec<_>>().iter()));
+        sut.push(800);
+        assert_eq!(sut[800], 800);
+
+        // differing block sizes
+        let inputs: Vec<Vector<usize>> = vec![(0..10).collect(), (10..1000).collect()];
+        let sut = Vector::pack(inputs);
+        assert!(sut.iter().eq((0..1000).collect::<Vec<_>>().iter()));
+        assert!(Vector::<usize>::pack(vec![]).is_empty());
     }
-}
 
-impl<T> IndexMut<usize> for CyclicArray<T> {
-    fn index_mut(&mut self, index: usize) -> &mut Self::Output {
-        let Some(item) = self.get_mut(index) else {
-            panic!("index out of bounds: {}", index);
+    #[cfg(feature = "metrics")]
+    #[test]
+    fn test_vector_pack_moves_blocks() {
+        let inputs = |sizes: [usize; 3]| -> Vec<Vector<usize>> {
+            let mut start = 0;
+            sizes
+                .iter()
+                .map(|size| {
+                    let mut input: Vector<usize> = Vector::with_capacity(256);
+                    input.extend(start..start + size);
+                    start += size;
+                    input
+                })
+                .collect()
         };
-        item
+        // counters of the inputs are carried into the result
+        let allocs = |inputs: &[Vector<usize>]| -> usize {
+            inputs.iter().map(|input| input.alloc_stats().allocs).sum()
+        };
+        // aligned inputs are moved without any allocation
+        let aligned = inputs([64, 96, 90]);
+        let before = allocs(&aligned);
+        let sut = Vector::pack(aligned);
+        assert_eq!(sut.alloc_stats().allocs, before);
+        assert_eq!(sut.tier_info().block_count, 16);
+        assert!(sut.iter().eq((0..250).collect::<Vec<_>>().iter()));
+
+        // blocks following a partial block are moved too, with elements
+        // shifted onto the previous block rather than copied to new blocks
+        for sizes in [[64, 90, 96], [50, 70, 90], [1, 15, 17]] {
+            let total: usize = sizes.iter().sum();
+            let unaligned = inputs(sizes);
+            let before = allocs(&unaligned);
+            let sut = Vector::pack(unaligned);
+            let stats = sut.alloc_stats();
+            assert_eq!(stats.allocs, before);
+            assert_eq!(stats.allocs - stats.deallocs, sut.tier_info().block_count);
+            assert_eq!(sut.tier_info().block_count, total.div_ceil(16));
+            assert!(sut.iter().eq((0..total).collect::<Vec<_>>().iter()));
+        }
     }
-}
 
-impl<T> Drop for CyclicArray<T> {
-    fn drop(&mut self) {
-        self.clear();
-        self.dealloc();
+    #[test]
+    fn test_vector_swap() {
+        let mut sut: Vector<String> = (0..200).map(|v| v.to_string()).collect();
+        // blocks hold 16 elements
+        sut.swap(15, 16);
+        assert_eq!(sut[15], "16");
+        assert_eq!(sut[16], "15");
+        sut.swap(0, 199);
+        assert_eq!(sut[0], "199");
+        assert_eq!(sut[199], "0");
+        sut.swap(100, 100);
+        assert_eq!(sut[100], "100");
+        assert_eq!(sut.len(), 200);
     }
-}
-
-#[cfg(test)]
-mod tests {
-    use super::*;
 
     #[test]
-    fn test_vector_insert_head() {
-        let mut sut = Vector::<usize>::new();
-        assert!(sut.is_empty());
-        for value in (1..=16).rev() {
-            sut.insert(0, value);
+    fn test_vector_swap_self() {
+        let mut sut: Vector<String> = (0..200).map(|v| v.to_string()).collect();
+        for index in [0, 15, 16, 199] {
+            sut.swap(index, index);
         }
-        assert!(!sut.is_empty());
-        for (index, value) in (1..=16).enumerate() {
-            assert_eq!(sut[index], value);
+        for (index, value) in sut.iter().enumerate() {
+            assert_eq!(*value, index.to_string());
         }
     }
 
     #[test]
-    fn test_vector_push_and_clear() {
-        let mut sut = Vector::<usize>::new();
-        assert!(sut.is_empty());
-        for value in 0..64 {
-            sut.push(value);
-        }
-        assert!(!sut.is_empty());
-        assert_eq!(sut.len(), 64);
-        assert_eq!(sut.capacity(), 64);
-        for value in 0..64 {
-            assert_eq!(sut[value], value);
+    #[should_panic(expected = "index out of bounds")]
+    fn test_vector_swap_self_out_of_bounds() {
+        let mut sut: Vector<usize> = (0..200).collect();
+        sut.swap(200, 200);
+    }
+
+    #[test]
+    #[should_panic(expected = "index out of bounds")]
+    fn test_vector_swap_panics() {
+        let mut sut: Vector<usize> = (0..200).collect();
+        sut.swap(0, 200);
+    }
+
+    #[test]
+    fn test_vector_swap_remove() {
+        let mut sut: Vector<usize> = (0..200).collect();
+        assert_eq!(sut.swap_remove(10), 10);
+        assert_eq!(sut[10], 199);
+        assert_eq!(sut.len(), 199);
+        // removing the last element simply pops it
+        assert_eq!(sut.swap_remove(198), 198);
+        assert_eq!(sut.len(), 198);
+        assert_eq!(sut[197], 197);
+        while !sut.is_empty() {
+            sut.swap_remove(0);
         }
-        sut.clear();
-        assert!(sut.is_empty());
-        assert_eq!(sut.len(), 0);
         assert_eq!(sut.capacity(), 0);
     }
 
     #[test]
-    fn test_vector_get_mut() {
-        let mut sut = Vector::<usize>::new();
-        for value in 0..4 {
+    #[should_panic(expected = "swap_remove index (is 10) should be < len (is 10)")]
+    fn test_vector_swap_remove_panics() {
+        let mut sut: Vector<usize> = (0..10).collect();
+        sut.swap_remove(10);
+    }
+
+    #[test]
+    fn test_vector_reserve_blocks_for() {
+        let mut sut: Vector<usize> = Vector::new();
+        sut.reserve_blocks_for(0);
+        assert_eq!(sut.capacity(), 0);
+        sut.reserve_blocks_for(10);
+        assert_eq!(sut.capacity(), 12);
+        for value in 0..12 {
             sut.push(value);
         }
-        if let Some(value) = sut.get_mut(1) {
-            *value = 11;
-        } else {
-            panic!("get_mut() returned None")
+        assert_eq!(sut.capacity(), 12);
+
+        let mut sut: Vector<usize> = (0..100).collect();
+        assert_eq!(sut.capacity(), 112);
+        sut.reserve_blocks_for(20);
+        assert_eq!(sut.capacity(), 144);
+        // existing empty blocks count toward the reservation
+        sut.reserve_blocks_for(17);
+        assert_eq!(sut.capacity(), 144);
+        let before = sut.block_exponent();
+        for value in 100..144 {
+            sut.push(value);
         }
-        sut[2] = 12;
-        assert_eq!(sut.len(), 4);
-        assert_eq!(sut[0], 0);
-        assert_eq!(sut[1], 11);
-        assert_eq!(sut[2], 12);
-        assert_eq!(sut[3], 3);
+        assert_eq!(sut.block_exponent(), before);
+        assert_eq!(sut.capacity(), 144);
+
+        // too many blocks for the block size
+        sut.reserve_blocks_for(500);
+        assert_eq!(sut.block_exponent(), 5);
+        assert_eq!(sut.capacity() % 32, 0);
+        assert!(sut.capacity() - sut.len() >= 500);
+        assert!(sut.iter().eq((0..144).collect::<Vec<_>>().iter()));
     }
 
     #[test]
-    fn test_vector_insert_expand() {
-        let mut sut = Vector::<usize>::new();
-        assert!(sut.is_empty());
-        for value in (1..=130).rev() {
-            sut.insert(0, value);
+    fn test_vector_contains() {
+        let sut: Vector<usize> = (0..1000).map(|v| v * 2).collect();
+        assert!(sut.contains(&0));
+        assert!(sut.contains(&1000));
+        assert!(sut.contains(&1998));
+        assert!(!sut.contains(&999));
+        assert!(!sut.contains(&2000));
+        assert!(!Vector::<usize>::new().contains(&0));
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_vector_group_by_key() {
+        let sut: Vector<usize> = (0..1000).collect();
+        let groups = sut.group_by_key(|v| v % 3);
+        assert_eq!(groups.len(), 3);
+        for (key, group) in groups.iter() {
+            let expected: Vec<usize> = (0..1000).filter(|v| v % 3 == *key).collect();
+            assert!(group.iter().eq(expected.iter()));
         }
-        assert!(!sut.is_empty());
-        assert_eq!(sut.len(), 130);
-        assert_eq!(sut.capacity(), 144);
-        for value in 0..130 {
-            assert_eq!(sut[value], value + 1);
+        assert_eq!(groups.values().map(|g| g.len()).sum::<usize>(), 1000);
+
+        let sut: Vector<String> =
+            Vector::from_iter(["b1", "a1", "b2", "c1", "a2"].map(String::from));
+        let groups = sut.group_by_key(|v| v.chars().next().unwrap());
+        assert!(groups[&'a'].iter().eq(["a1", "a2"].iter()));
+        assert!(groups[&'b'].iter().eq(["b1", "b2"].iter()));
+        assert!(groups[&'c'].iter().eq(["c1"].iter()));
+        assert!(Vector::<usize>::new().group_by_key(|v| *v).is_empty());
+    }
+
+    #[test]
+    fn test_vector_optimize_after_load() {
+        // lands just past a block boundary with blocks larger than needed
+        let mut sut: Vector<usize> = Vector::with_capacity(1000);
+        sut.extend(0..200);
+        assert_eq!(sut.block_exponent(), 5);
+        assert_eq!(sut.capacity(), 1024);
+        sut.optimize_after_load();
+        assert_eq!(sut.block_exponent(), 4);
+        assert_eq!(sut.capacity(), 208);
+        assert!(sut.iter().eq((0..200).collect::<Vec<_>>().iter()));
+        sut.push(200);
+        assert_eq!(sut[200], 200);
+
+        // already well fitted
+        let mut sut: Vector<usize> = (0..1000).collect();
+        let info = sut.tier_info();
+        sut.optimize_after_load();
+        assert_eq!(sut.tier_info(), info);
+        let mut sut: Vector<usize> = Vector::with_capacity(1000);
+        sut.extend(0..250);
+        sut.optimize_after_load();
+        assert_eq!(sut.block_exponent(), 5);
+        assert_eq!(sut.capacity(), 256);
+        assert!(sut.iter().eq((0..250).collect::<Vec<_>>().iter()));
+    }
+
+    #[test]
+    fn test_vector_split_on() {
+        let values = [0, 1, 2, 0, 0, 3, 4, 5, 0];
+        let sut: Vector<usize> = Vector::from_iter(values);
+        let actual: Vec<Vec<usize>> = sut
+            .split_on(&0)
+            .map(|s| s.iter().copied().collect())
+            .collect();
+        let expected: Vec<Vec<usize>> = values.split(|v| *v == 0).map(|s| s.to_vec()).collect();
+        assert_eq!(actual, expected);
+        assert_eq!(
+            actual,
+            vec![vec![], vec![1, 2], vec![], vec![3, 4, 5], vec![]]
+        );
+
+        // runs spanning block boundaries
+        let sut: Vector<usize> = (1..1000).map(|v| v % 100).collect();
+        let slices: Vec<VectorSlice<'_, usize>> = sut.split_on(&0).collect();
+        assert_eq!(slices.len(), 10);
+        assert_eq!(slices[0].range(), 0..99);
+        assert_eq!(slices[1].range(), 100..199);
+        assert_eq!(slices[1].get(0), Some(&1));
+        assert_eq!(slices[1].get(98), Some(&99));
+        assert_eq!(slices[1].get(99), None);
+        assert!(slices[1].iter().eq((1..100).collect::<Vec<_>>().iter()));
+        assert_eq!(slices[9].len(), 99);
+
+        let empty: Vector<usize> = Vector::new();
+        let slices: Vec<VectorSlice<'_, usize>> = empty.split_on(&0).collect();
+        assert_eq!(slices.len(), 1);
+        assert!(slices[0].is_empty());
+    }
+
+    #[test]
+    fn test_vector_sort_unstable() {
+        let mut values: Vec<usize> = (0..5000).collect();
+        for i in (1..values.len()).rev() {
+            values.swap(i, rand::random_range(0..=i));
         }
+        let mut sut: Vector<usize> = Vector::from_iter(values.iter().copied());
+        sut.sort_unstable();
+        assert!(sut.iter().eq((0..5000).collect::<Vec<_>>().iter()));
+        sut.sort_unstable_by(|a, b| b.cmp(a));
+        assert!(sut.iter().eq((0..5000).rev().collect::<Vec<_>>().iter()));
+        sut.sort_unstable_by_key(|v| *v % 1000);
+        assert!(sut.is_sorted_by(|a, b| a % 1000 <= b % 1000));
+        assert_eq!(sut.len(), 5000);
+
+        let mut sut: Vector<usize> = Vector::from_iter(values);
+        sut.sort();
+        assert!(sut.iter().eq((0..5000).collect::<Vec<_>>().iter()));
     }
 
     #[test]
-    fn test_vector_push_many() {
-        let mut sut = Vector::<usize>::new();
+    fn test_vector_into_vec() {
+        // single block starting at the beginning of its buffer
+        let sut: Vector<String> = (0..3).map(|v| v.to_string()).collect();
+        let buffer = sut.block_of(0).unwrap().0.as_slices().0.as_ptr();
+        let actual: Vec<String> = sut.into();
+        assert_eq!(actual.as_ptr(), buffer);
+        assert_eq!(actual.capacity(), 4);
+        assert_eq!(actual, vec!["0", "1", "2"]);
+
+        // single block with spare blocks following
+        let mut sut: Vector<usize> = Vector::with_capacity(100);
+        sut.extend(0..10);
+        let buffer = sut.block_of(0).unwrap().0.as_slices().0.as_ptr();
+        let actual: Vec<usize> = sut.into();
+        assert_eq!(actual.as_ptr(), buffer);
+        assert_eq!(actual, (0..10).collect::<Vec<_>>());
+
+        // head is not at the start of the buffer
+        let mut sut: Vector<usize> = (1..3).collect();
+        sut.insert(0, 0);
+        let actual: Vec<usize> = sut.into();
+        assert_eq!(actual, vec![0, 1, 2]);
+
+        // many blocks
+        let sut: Vector<String> = (0..1000).map(|v| v.to_string()).collect();
+        let actual: Vec<String> = sut.into();
+        assert_eq!(actual.len(), 1000);
+        assert_eq!(actual[999], "999");
+
+        let actual: Vec<usize> = Vector::new().into();
+        assert!(actual.is_empty());
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_vector_serde() {
+        let sut: Vector<u64> = (0..1000).map(|v| v * 3).collect();
+        let json = serde_json::to_string(&sut).unwrap();
+        assert!(json.starts_with("[0,3,6,"));
+        let actual: Vector<u64> = serde_json::from_str(&json).unwrap();
+        assert_eq!(actual, sut);
+
+        let empty: Vector<String> = Vector::new();
+        let json = serde_json::to_string(&empty).unwrap();
+        assert_eq!(json, "[]");
+        let actual: Vector<String> = serde_json::from_str(&json).unwrap();
+        assert!(actual.is_empty());
+        assert!(serde_json::from_str::<Vector<u64>>("{}").is_err());
+    }
+
+    #[test]
+    fn test_vector_split_off() {
+        for at in [0, 1, 31, 32, 33, 500, 999, 1000] {
+            let mut sut: Vector<usize> = (0..1000).collect();
+            // rotate some blocks so that their elements wrap around
+            sut.insert(0, 0);
+            sut.remove(0);
+            let other = sut.split_off(at);
+            assert_eq!(sut.len(), at);
+            assert_eq!(other.len(), 1000 - at);
+            assert!(sut.iter().eq((0..at).collect::<Vec<_>>().iter()));
+            assert!(other.iter().eq((at..1000).collect::<Vec<_>>().iter()));
+            if other.len() > 32 {
+                assert_eq!(other.min_block_fill(), Some(1.0));
+            }
+            sut.extend(other);
+            assert!(sut.iter().eq((0..1000).collect::<Vec<_>>().iter()));
+        }
+
+        // both halves remain usable
+        let mut sut: Vector<String> = (0..100).map(|v| v.to_string()).collect();
+        let mut other = sut.split_off(50);
+        sut.push("a".to_owned());
+        other.insert(0, "b".to_owned());
+        other.remove(10);
+        assert_eq!(sut[50], "a");
+        assert_eq!(other[0], "b");
+        assert_eq!(other.len(), 50);
+    }
+
+    #[test]
+    #[should_panic(expected = "`at` split index (is 11) should be <= len (is 10)")]
+    fn test_vector_split_off_panics() {
+        let mut sut: Vector<usize> = (0..10).collect();
+        sut.split_off(11);
+    }
+
+    #[test]
+    fn test_vector_append() {
+        // differing block sizes
+        let mut sut: Vector<usize> = (0..3000).collect();
+        let mut other: Vector<usize> = (3000..8000).collect();
+        sut.append(&mut other);
+        assert!(other.is_empty());
+        assert_eq!(other.len(), 0);
+        assert_eq!(sut.len(), 8000);
+        assert!(sut.iter().eq((0..8000).collect::<Vec<_>>().iter()));
+
+        // same block size with a partially filled last block
+        let mut sut: Vector<usize> = (0..2000).collect();
+        let mut other: Vector<usize> = (2000..3500).collect();
+        assert_eq!(sut.block_exponent(), other.block_exponent());
+        sut.append(&mut other);
+        assert!(other.is_empty());
+        assert!(sut.iter().eq((0..3500).collect::<Vec<_>>().iter()));
+        assert_eq!(sut.min_block_fill(), Some(1.0));
+        assert_eq!(sut.capacity(), 3520);
+        sut.push(3500);
+        assert_eq!(sut[3500], 3500);
+
+        // same block size ending on a block boundary
+        let mut sut: Vector<usize> = (0..1024).collect();
+        let mut other: Vector<usize> = (1024..2000).collect();
+        sut.append(&mut other);
+        assert!(sut.iter().eq((0..2000).collect::<Vec<_>>().iter()));
+        assert_eq!(sut.min_block_fill(), Some(1.0));
+
+        // appending to and from an empty vector
+        let mut empty: Vector<usize> = Vector::new();
+        empty.append(&mut sut);
         assert!(sut.is_empty());
-        for value in 0..100_000 {
-            sut.push(value);
+        assert_eq!(empty.len(), 2000);
+        empty.append(&mut sut);
+        assert_eq!(empty.len(), 2000);
+        other.push(1);
+        assert_eq!(other.len(), 1);
+    }
+
+    #[test]
+    fn test_vector_from_vec() {
+        let expected: Vec<String> = (0..10_000).map(|v| v.to_string()).collect();
+        let sut: Vector<String> = Vector::from(expected.clone());
+        assert_eq!(sut.len(), 10_000);
+        assert!(sut.iter().eq(expected.iter()));
+        let actual: Vec<String> = sut.into();
+        assert_eq!(actual, expected);
+
+        let sut: Vector<usize> = Vec::new().into();
+        assert!(sut.is_empty());
+        assert_eq!(sut.capacity(), 0);
+    }
+
+    #[test]
+    fn test_vector_from_array_and_slice() {
+        let sut = Vector::from([1, 2, 3, 4, 5]);
+        assert_eq!(sut.len(), 5);
+        for index in 0..5 {
+            assert_eq!(sut[index], index + 1);
         }
-        assert!(!sut.is_empty());
-        assert_eq!(sut.len(), 100_000);
-        assert_eq!(sut.capacity(), 100352);
-        for value in 0..100_000 {
-            assert_eq!(sut[value], value);
+        let sut: Vector<usize> = Vector::from([]);
+        assert!(sut.is_empty());
+
+        let values: Vec<String> = (0..1000).map(|v| v.to_string()).collect();
+        let sut = Vector::from(&values[..]);
+        assert_eq!(sut.len(), 1000);
+        assert!(sut.iter().eq(values.iter()));
+        assert_eq!(sut.capacity(), 1024);
+    }
+
+    #[test]
+    fn test_vector_blocks() {
+        let mut sut: Vector<usize> = Vector::with_capacity(100);
+        sut.extend(0..40);
+        // blocks hold 16 elements, the last is partially filled
+        let blocks: Vec<(&[usize], &[usize])> = sut.blocks().collect();
+        assert_eq!(blocks.len(), 3);
+        assert!(blocks.iter().all(|(_, second)| second.is_empty()));
+        assert_eq!(blocks[2].0, &[32, 33, 34, 35, 36, 37, 38, 39][..]);
+
+        // inserting at the front wraps the blocks around their buffers
+        sut.insert(0, 100);
+        let blocks: Vec<(&[usize], &[usize])> = sut.blocks().collect();
+        assert_eq!(blocks[1].0, &[15][..]);
+        assert_eq!(blocks[1].1.len(), 15);
+        let flattened: Vec<usize> = sut
+            .blocks()
+            .flat_map(|(first, second)| first.iter().chain(second.iter()))
+            .copied()
+            .collect();
+        assert!(sut.iter().eq(flattened.iter()));
+        assert_eq!(Vector::<usize>::new().blocks().count(), 0);
+    }
+
+    #[test]
+    fn test_vector_rotate() {
+        let expected: Vec<usize> = (0..1000).collect();
+        for amount in [0, 1, 31, 32, 500, 999, 1000] {
+            let mut sut: Vector<usize> = (0..1000).collect();
+            sut.rotate_left(amount);
+            let mut rotated = expected.clone();
+            rotated.rotate_left(amount);
+            assert!(sut.iter().eq(rotated.iter()), "rotate_left({amount})");
+
+            let mut sut: Vector<usize> = (0..1000).collect();
+            sut.rotate_right(amount);
+            let mut rotated = expected.clone();
+            rotated.rotate_right(amount);
+            assert!(sut.iter().eq(rotated.iter()), "rotate_right({amount})");
         }
+        let mut sut: Vector<usize> = (0..1000).collect();
+        sut.reverse();
+        assert!(sut.iter().eq(expected.iter().rev()));
+        let mut empty: Vector<usize> = Vector::new();
+        empty.rotate_left(0);
+        empty.reverse();
+        assert!(empty.is_empty());
     }
 
     #[test]
-    fn test_vector_push_within_capacity() {
-        // empty array has no allocated space
-        let mut sut = Vector::<u32>::new();
-        assert_eq!(sut.push_within_capacity(101), Err(101));
-        sut.push(1);
-        sut.push(2);
-        assert_eq!(sut.push_within_capacity(3), Ok(()));
-        assert_eq!(sut.push_within_capacity(4), Ok(()));
-        assert_eq!(sut.push_within_capacity(5), Err(5));
+    #[should_panic(expected = "rotation (is 11) should be <= len (is 10)")]
+    fn test_vector_rotate_panics() {
+        let mut sut: Vector<usize> = (0..10).collect();
+        sut.rotate_right(11);
     }
 
     #[test]
-    fn test_vector_remove_small() {
-        let mut sut = Vector::<usize>::new();
-        assert!(sut.is_empty());
-        assert_eq!(sut.len(), 0);
-        for value in 0..15 {
-            sut.push(value);
-        }
-        assert!(!sut.is_empty());
-        assert_eq!(sut.len(), 15);
-        for value in 0..15 {
-            assert_eq!(sut.remove(0), value);
-        }
-        assert!(sut.is_empty());
-        assert_eq!(sut.len(), 0);
-        assert_eq!(sut.capacity(), 0);
+    fn test_vector_iter_exact_size() {
+        let mut sut: Vector<usize> = (0..1000).collect();
+        let mut iter = sut.iter();
+        assert_eq!(iter.size_hint(), (1000, Some(1000)));
+        assert_eq!(iter.len(), 1000);
+        iter.nth(499);
+        assert_eq!(iter.len(), 500);
+        assert_eq!(iter.by_ref().count(), 500);
+        assert_eq!(iter.len(), 0);
+        assert_eq!(iter.next(), None);
+        assert_eq!(iter.next(), None);
+
+        let mut iter = sut.iter_mut();
+        assert_eq!(iter.size_hint(), (1000, Some(1000)));
+        iter.nth(9);
+        assert_eq!(iter.len(), 990);
+        assert_eq!(iter.by_ref().count(), 990);
+        assert!(iter.next().is_none());
+        assert!(iter.next().is_none());
+
+        let mut iter = sut.into_iter();
+        assert_eq!(iter.len(), 1000);
+        iter.next();
+        iter.next_back();
+        assert_eq!(iter.len(), 998);
+        assert_eq!(iter.by_ref().count(), 998);
+        assert_eq!(iter.next(), None);
+        assert_eq!(iter.next(), None);
     }
 
     #[test]
-    fn test_vector_remove_medium() {
-        let mut sut = Vector::<usize>::new();
-        assert!(sut.is_empty());
-        assert_eq!(sut.len(), 0);
-        assert_eq!(sut.capacity(), 0);
-        for value in 0..2048 {
-            sut.push(value);
+    fn test_vector_push_pop_front() {
+        let mut sut: Vector<usize> = Vector::new();
+        assert_eq!(sut.pop_front(), None);
+        for value in 0..1000 {
+            sut.push_front(value);
         }
-        assert!(!sut.is_empty());
-        assert_eq!(sut.len(), 2048);
-        assert_eq!(sut.capacity(), 2048);
-        for value in 0..2048 {
-            assert_eq!(sut.remove(0), value);
+        assert_eq!(sut.len(), 1000);
+        for (index, value) in sut.iter().enumerate() {
+            assert_eq!(*value, 999 - index);
+        }
+        assert_eq!(sut.pop_front(), Some(999));
+        assert_eq!(sut.pop(), Some(0));
+        for value in (1..999).rev() {
+            assert_eq!(sut.pop_front(), Some(value));
         }
         assert!(sut.is_empty());
-        assert_eq!(sut.len(), 0);
+        assert_eq!(sut.pop_front(), None);
+        // compress as elements are removed from the front
         assert_eq!(sut.capacity(), 0);
     }
 
     #[test]
-    fn test_vector_expand_and_compress() {
-        // add enough to cause multiple expansions
-        let mut sut = Vector::<usize>::new();
-        for value in 0..1024 {
+    fn test_vector_get_disjoint_mut() {
+        let mut sut: Vector<usize> = (0..1000).collect();
+        let [a, b] = sut.get_disjoint_mut([3, 900]).unwrap();
+        std::mem::swap(a, b);
+        *a += 1;
+        assert_eq!(sut[3], 901);
+        assert_eq!(sut[900], 3);
+        assert!(sut.get_disjoint_mut([3, 1000]).is_none());
+        assert!(sut.get_disjoint_mut([5, 6, 5]).is_none());
+        assert!(sut.get_disjoint_mut::<0>([]).is_some());
+        let [x, y, z] = sut.get_disjoint_mut([0, 999, 500]).unwrap();
+        assert_eq!((*x, *y, *z), (0, 999, 500));
+    }
+
+    #[test]
+    fn test_vector_range() {
+        let mut sut: Vector<usize> = (0..1000).collect();
+        assert_eq!(sut.range(100..200).count(), 100);
+        assert_eq!(sut.range(100..200).len(), 100);
+        assert!(sut.range(100..200).copied().eq(100..200));
+        assert!(sut.range(..=10).copied().eq(0..=10));
+        assert!(sut.range(990..).copied().eq(990..1000));
+        assert_eq!(sut.range(500..500).next(), None);
+        assert_eq!(sut.range(..).count(), 1000);
+
+        // rotate so the blocks wrap around within their buffers
+        for _ in 0..7 {
+            let value = sut.pop_front().unwrap();
             sut.push(value);
         }
-        assert_eq!(sut.len(), 1024);
-        assert_eq!(sut.capacity(), 1024);
-        // remove enough to cause multiple compressions
-        for _ in 0..960 {
-            sut.pop();
+        for value in sut.range_mut(100..200) {
+            *value = 0;
         }
-        // ensure the correct elements remain
-        assert_eq!(sut.len(), 64);
-        assert_eq!(sut.capacity(), 64);
-        for value in 0..64 {
-            assert_eq!(sut[value], value);
+        for (index, value) in sut.iter().enumerate() {
+            if (100..200).contains(&index) {
+                assert_eq!(*value, 0);
+            } else {
+                assert_eq!(*value, (index + 7) % 1000);
+            }
         }
+        assert_eq!(sut.range_mut(1000..).count(), 0);
+        assert_eq!(sut.range_mut(999..).len(), 1);
     }
 
     #[test]
-    fn test_vector_pop_small() {
-        let mut sut = Vector::<usize>::new();
+    #[should_panic(expected = "range end (is 1001) should be <= len (is 1000)")]
+    fn test_vector_range_out_of_bounds() {
+        let sut: Vector<usize> = (0..1000).collect();
+        sut.range(10..1001);
+    }
+
+    #[test]
+    fn test_vector_memory_usage() {
+        let mut sut: Vector<u64> = Vector::new();
+        assert_eq!(sut.memory_usage(), 0);
+        assert_eq!(sut.overhead(), 0);
+        let block_bytes = std::mem::size_of::<CyclicArray<u64>>();
+        for n in [1_000, 10_000, 100_000, 1_000_000] {
+            while sut.len() < n {
+                sut.push(sut.len() as u64);
+            }
+            let used = n * std::mem::size_of::<u64>();
+            let dope = sut.index.len() * block_bytes;
+            assert_eq!(sut.memory_usage(), used + dope + sut.overhead());
+            // overhead is on the order of O(√N) elements and dope entries
+            let bound = 8 * (n as f64).sqrt() as usize * (8 + block_bytes);
+            assert!(sut.overhead() <= bound, "{} > {bound}", sut.overhead());
+        }
+        sut.clear();
+        assert_eq!(sut.overhead(), sut.memory_usage());
+    }
+
+    #[test]
+    fn test_vector_with_tier_exponent() {
+        let mut sut: Vector<usize> = Vector::with_tier_exponent(6);
         assert!(sut.is_empty());
-        assert_eq!(sut.len(), 0);
-        for value in 0..15 {
+        assert_eq!(sut.capacity(), 0);
+        let before = sut.block_exponent();
+        for value in 0..4096 {
             sut.push(value);
         }
-        assert!(!sut.is_empty());
-        assert_eq!(sut.len(), 15);
-        for value in (0..15).rev() {
-            assert_eq!(sut.pop(), Some(value));
+        assert_eq!(sut.block_exponent(), before);
+        assert_eq!(sut.tier_info().k, 6);
+        assert_eq!(sut.tier_info().block_count, 64);
+        // grows from there as usual but never shrinks below the given size
+        sut.push(4096);
+        assert_eq!(sut.tier_info().k, 7);
+        while sut.len() > 100 {
+            sut.pop();
         }
-        assert!(sut.is_empty());
-        assert_eq!(sut.len(), 0);
-        assert_eq!(sut.capacity(), 0);
+        assert_eq!(sut.tier_info().k, 6);
+        for (index, value) in sut.iter().enumerate() {
+            assert_eq!(*value, index);
+        }
+        sut.shrink_to_fit();
+        assert_eq!(sut.tier_info().k, 6);
+        sut.clear();
+        assert_eq!(sut.tier_info().k, 6);
     }
 
     #[test]
-    fn test_vector_pop_if() {
-        let mut sut = Vector::<u32>::new();
-        assert!(sut.pop_if(|_| panic!("should not be called")).is_none());
-        for value in 0..10 {
-            sut.push(value);
+    fn test_vector_with_tier_exponent_remove() {
+        let mut sut: Vector<usize> = Vector::with_tier_exponent(6);
+        sut.extend(0..100);
+        assert_eq!(sut.tier_info().k, 6);
+        sut.remove(50);
+        assert_eq!(sut.tier_info().k, 6);
+        sut.truncate(10);
+        assert_eq!(sut.tier_info().k, 6);
+        while sut.pop().is_some() {
+            assert_eq!(sut.tier_info().k, 6);
         }
-        assert!(sut.pop_if(|_| false).is_none());
-        let maybe = sut.pop_if(|v| *v == 9);
-        assert_eq!(maybe.unwrap(), 9);
-        assert!(sut.pop_if(|v| *v == 9).is_none());
+        sut.extend(0..10);
+        let mut cloned = sut.clone();
+        cloned.remove(0);
+        assert_eq!(cloned.tier_info().k, 6);
+        assert_eq!(cloned.len(), 9);
     }
 
     #[test]
-    fn test_vector_iter() {
-        let mut sut = Vector::<usize>::new();
+    #[should_panic(expected = "tier exponent (is 1) should be >= 2 and < 32")]
+    fn test_vector_with_tier_exponent_too_small() {
+        Vector::<usize>::with_tier_exponent(1);
+    }
+
+    #[cfg(feature = "allocator-api2")]
+    #[test]
+    fn test_vector_allocator() {
+        use allocator_api2::alloc::{AllocError, Allocator, Global};
+        use std::cell::Cell;
+        use std::rc::Rc;
+
+        // counts the allocations performed through it
+        #[derive(Clone, Default)]
+        struct Counting {
+            allocs: Rc<Cell<usize>>,
+            deallocs: Rc<Cell<usize>>,
+        }
+
+        impl Counting {
+            fn live(&self) -> usize {
+                self.allocs.get() - self.deallocs.get()
+            }
+        }
+
+        unsafe impl Allocator for Counting {
+            fn allocate(&self, layout: Layout) -> Result<NonNull<[u8]>, AllocError> {
+                self.allocs.set(self.allocs.get() + 1);
+                Global.allocate(layout)
+            }
+
+            unsafe fn deallocate(&self, ptr: NonNull<u8>, layout: Layout) {
+                self.deallocs.set(self.deallocs.get() + 1);
+                unsafe { Global.deallocate(ptr, layout) }
+            }
+        }
+
+        let counting = Counting::default();
+        let mut sut: Vector<usize, Counting> = Vector::new_in(counting.clone());
         for value in 0..1000 {
             sut.push(value);
         }
-        assert_eq!(sut.len(), 1000);
-        for (index, value) in sut.iter().enumerate() {
-            assert_eq!(sut[index], *value);
+        assert!(counting.allocs.get() > 0);
+        assert_eq!(counting.live(), sut.tier_info().block_count);
+        #[cfg(feature = "metrics")]
+        assert_eq!(sut.alloc_stats().allocs, counting.allocs.get());
+        while sut.len() > 10 {
+            sut.pop();
         }
+        assert_eq!(counting.live(), sut.tier_info().block_count);
+        #[cfg(feature = "metrics")]
+        assert_eq!(sut.alloc_stats().deallocs, counting.deallocs.get());
+
+        // derived vectors allocate through the same allocator
+        let mut other = sut.clone();
+        let tail = other.split_off(5);
+        assert!(tail.iter().copied().eq(5..10));
+        let blocks = sut.tier_info().block_count
+            + other.tier_info().block_count
+            + tail.tier_info().block_count;
+        assert_eq!(counting.live(), blocks);
+        drop(tail);
+        other.extend_front(100..200);
+        assert_eq!(other.drain(..50).count(), 50);
+        drop(other);
+        assert_eq!(counting.live(), sut.tier_info().block_count);
+        drop(sut);
+        assert_eq!(counting.live(), 0);
+
+        let mut sut: Vector<String, Counting> = Vector::with_capacity_in(100, counting.clone());
+        assert_eq!(counting.live(), sut.tier_info().block_count);
+        sut.extend((0..100).map(|v| v.to_string()));
+        let mut iter = sut.into_iter();
+        assert_eq!(iter.next(), Some("0".to_owned()));
+        assert_eq!(iter.next_back(), Some("99".to_owned()));
+        drop(iter);
+        assert_eq!(counting.live(), 0);
     }
 
     #[test]
@@ -1081,6 +7826,214 @@ mod tests {
         // sut.len(); // error: ownership of sut was moved
     }
 
+    #[test]
+    fn test_vector_into_iter_back_full() {
+        let sut: Vector<usize> = (0..1000).collect();
+        let values: Vec<usize> = sut.into_iter_back().collect();
+        let expected: Vec<usize> = (0..1000).rev().collect();
+        assert_eq!(values, expected);
+    }
+
+    /// Increments the shared counter when dropped.
+    struct DropCounter(std::rc::Rc<std::cell::Cell<usize>>);
+
+    impl Drop for DropCounter {
+        fn drop(&mut self) {
+            self.0.set(self.0.get() + 1);
+        }
+    }
+
+    #[test]
+    fn test_vector_into_iter_back_partial_drop() {
+        let dropped = std::rc::Rc::new(std::cell::Cell::new(0));
+        let mut sut: Vector<DropCounter> = Vector::new();
+        for _ in 0..500 {
+            sut.push(DropCounter(dropped.clone()));
+        }
+        let mut iter = sut.into_iter_back();
+        for _ in 0..100 {
+            iter.next();
+        }
+        for _ in 0..50 {
+            iter.next_back();
+        }
+        assert_eq!(dropped.get(), 150);
+        drop(iter);
+        assert_eq!(dropped.get(), 500);
+    }
+
+    #[test]
+    fn test_vector_into_iter_interleaved() {
+        let sut: Vector<String> = (0..300).map(|v| v.to_string()).collect();
+        let mut iter = sut.into_iter();
+        let mut front = 0;
+        let mut back = 300;
+        while front < back {
+            if rand::random_bool(0.5) {
+                assert_eq!(iter.next(), Some(front.to_string()));
+                front += 1;
+            } else {
+                back -= 1;
+                assert_eq!(iter.next_back(), Some(back.to_string()));
+            }
+        }
+        assert_eq!(iter.next(), None);
+        assert_eq!(iter.next_back(), None);
+    }
+
+    #[test]
+    fn test_vector_apply_permutation() {
+        let original: Vec<String> = (0..500).map(|v| v.to_string()).collect();
+        let reference =
+            |perm: &[usize]| -> Vec<String> { perm.iter().map(|&p| original[p].clone()).collect() };
+
+        // reverse
+        let mut sut: Vector<String> = original.iter().cloned().collect();
+        let perm: Vec<usize> = (0..500).rev().collect();
+        sut.apply_permutation(&perm);
+        assert_eq!(
+            sut.iter().cloned().collect::<Vec<String>>(),
+            reference(&perm)
+        );
+
+        // identity
+        let mut sut: Vector<String> = original.iter().cloned().collect();
+        let perm: Vec<usize> = (0..500).collect();
+        sut.apply_permutation(&perm);
+        assert_eq!(sut.iter().cloned().collect::<Vec<String>>(), original);
+
+        // random
+        let mut sut: Vector<String> = original.iter().cloned().collect();
+        let mut perm: Vec<usize> = (0..500).collect();
+        for i in (1..perm.len()).rev() {
+            perm.swap(i, rand::random_range(0..=i));
+        }
+        sut.apply_permutation(&perm);
+        assert_eq!(
+            sut.iter().cloned().collect::<Vec<String>>(),
+            reference(&perm)
+        );
+
+        let mut empty: Vector<usize> = Vector::new();
+        empty.apply_permutation(&[]);
+        assert!(empty.is_empty());
+    }
+
+    #[test]
+    #[should_panic(expected = "permutation length (is 2) should be == len (is 3)")]
+    fn test_vector_apply_permutation_length_panics() {
+        let mut sut: Vector<usize> = (0..3).collect();
+        sut.apply_permutation(&[1, 0]);
+    }
+
+    #[cfg(debug_assertions)]
+    #[test]
+    #[should_panic(expected = "perm is not a permutation of 0..len")]
+    fn test_vector_apply_permutation_duplicate_panics() {
+        let mut sut: Vector<usize> = (0..3).collect();
+        sut.apply_permutation(&[1, 0, 1]);
+    }
+
+    #[cfg(not(debug_assertions))]
+    #[test]
+    fn test_vector_apply_permutation_invalid_terminates() {
+        let mut sut: Vector<usize> = (0..2).collect();
+        sut.apply_permutation(&[1, 1]);
+        assert_eq!(sut.len(), 2);
+        let mut sut: Vector<usize> = (0..100).collect();
+        sut.apply_permutation(&[0; 100]);
+        assert_eq!(sut.len(), 100);
+    }
+
+    #[test]
+    fn test_vector_iter_physical() {
+        let mut sut = Vector::<usize>::new();
+        for value in 0..64 {
+            sut.push(value);
+        }
+        // freshly built blocks start at the head of each buffer
+        for (index, (block, slot, value)) in sut.iter_physical().enumerate() {
+            assert_eq!(*value, index);
+            assert_eq!(block, index / 8);
+            assert_eq!(slot, index % 8);
+        }
+
+        // inserting at the front pushes elements onto the front of each of the
+        // following blocks, causing their contents to wrap around
+        sut.pop();
+        sut.insert(0, 100);
+        let physical: Vec<(usize, usize, usize)> =
+            sut.iter_physical().map(|(b, s, v)| (b, s, *v)).collect();
+        assert_eq!(physical.len(), 64);
+        assert_eq!(physical[0], (0, 0, 100));
+        assert_eq!(physical[1], (0, 1, 0));
+        assert_eq!(physical[8], (1, 7, 7));
+        assert_eq!(physical[9], (1, 0, 8));
+        for (index, (block, _, value)) in physical.iter().enumerate().skip(1) {
+            assert_eq!(*value, index - 1);
+            assert_eq!(*block, index / 8);
+        }
+    }
+
+    #[test]
+    fn test_vector_swap_ranges() {
+        let mut sut: Vector<usize> = (0..100).collect();
+        let mut reference: Vec<usize> = (0..100).collect();
+        // ranges in different blocks (16 elements each)
+        sut.swap_ranges(5, 60, 20);
+        let (left, right) = reference.split_at_mut(60);
+        left[5..25].swap_with_slice(&mut right[..20]);
+        assert!(sut.iter().eq(reference.iter()));
+        // ranges within one block
+        sut.swap_ranges(34, 33, 0);
+        sut.swap_ranges(36, 33, 2);
+        reference.swap(33, 36);
+        reference.swap(34, 37);
+        assert!(sut.iter().eq(reference.iter()));
+    }
+
+    #[test]
+    #[should_panic(expected = "ranges starting at 10 and 5 with length 6 overlap")]
+    fn test_vector_swap_ranges_overlap_panics() {
+        let mut sut: Vector<usize> = (0..100).collect();
+        sut.swap_ranges(10, 5, 6);
+    }
+
+    #[test]
+    #[should_panic(expected = "range 90..90+20 out of bounds for length 100")]
+    fn test_vector_swap_ranges_bounds_panics() {
+        let mut sut: Vector<usize> = (0..100).collect();
+        sut.swap_ranges(0, 90, 20);
+    }
+
+    #[test]
+    fn test_vector_into_chunks_exact() {
+        let sut: Vector<String> = (0..100).map(|v| v.to_string()).collect();
+        let mut chunks = sut.into_chunks_exact::<4>();
+        for chunk in 0..25 {
+            let array = chunks.next().unwrap();
+            for (offset, value) in array.iter().enumerate() {
+                assert_eq!(*value, (chunk * 4 + offset).to_string());
+            }
+        }
+        assert!(chunks.next().is_none());
+        assert!(chunks.remainder().is_empty());
+
+        let sut: Vector<String> = (0..103).map(|v| v.to_string()).collect();
+        let mut chunks = sut.into_chunks_exact::<10>();
+        let arrays: Vec<[String; 10]> = chunks.by_ref().collect();
+        assert_eq!(arrays.len(), 10);
+        assert_eq!(arrays[9][9], "99");
+        let remainder = chunks.remainder();
+        assert_eq!(remainder, vec!["100", "101", "102"]);
+
+        // remainder skips over the arrays that were not consumed
+        let sut: Vector<usize> = (0..23).collect();
+        let chunks = sut.into_chunks_exact::<5>();
+        assert_eq!(chunks.size_hint(), (4, Some(4)));
+        assert_eq!(chunks.remainder(), vec![20, 21, 22]);
+    }
+
     #[test]
     fn test_vector_remove_insert_basic() {
         let mut sut = Vector::<usize>::new();
@@ -1130,6 +8083,131 @@ mod tests {
         }
     }
 
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_indexed_vector_membership() {
+        let mut sut = IndexedVector::<String>::new();
+        assert!(sut.is_empty());
+        for value in 0..100 {
+            sut.push(value.to_string());
+        }
+        sut.insert(0, "a".to_owned());
+        sut.insert(50, "b".to_owned());
+        sut.insert(10, "a".to_owned());
+        assert_eq!(sut.len(), 103);
+        assert!(sut.contains(&"a".to_owned()));
+        assert!(sut.contains(&"b".to_owned()));
+        assert!(!sut.contains(&"c".to_owned()));
+
+        // removing one duplicate leaves the other as a member
+        assert_eq!(sut.remove(0), "a");
+        assert!(sut.contains(&"a".to_owned()));
+        let at = sut.iter().position(|v| v == "a").unwrap();
+        assert_eq!(sut.remove(at), "a");
+        assert!(!sut.contains(&"a".to_owned()));
+        assert_eq!(sut.pop(), Some("99".to_owned()));
+        assert!(!sut.contains(&"99".to_owned()));
+
+        // membership agrees with the ordered contents
+        for _ in 0..100 {
+            let from = rand::random_range(0..sut.len());
+            let value = sut.remove(from);
+            if rand::random_bool(0.5) {
+                let to = rand::random_range(0..=sut.len());
+                sut.insert(to, value);
+            }
+        }
+        for value in 0..100 {
+            let key = value.to_string();
+            assert_eq!(sut.contains(&key), sut.iter().any(|v| *v == key));
+        }
+        while sut.pop().is_some() {}
+        assert!(!sut.contains(&"b".to_owned()));
+    }
+
+    #[cfg(feature = "rayon")]
+    #[test]
+    fn test_vector_par_iter() {
+        use rayon::prelude::*;
+
+        let mut sut: Vector<usize> = (0..10_000).collect();
+        let sum: usize = sut.par_iter().map(|v| v * 2).sum();
+        assert_eq!(sum, 9_999 * 10_000);
+        sut.par_iter_mut().for_each(|v| *v += 1);
+        for (index, value) in sut.iter().enumerate() {
+            assert_eq!(*value, index + 1);
+        }
+        let empty: Vector<usize> = Vector::new();
+        assert_eq!(empty.par_iter().count(), 0);
+    }
+
+    #[cfg(feature = "arc-swap")]
+    #[test]
+    fn test_published_vector() {
+        use std::sync::Arc;
+        use std::sync::atomic::{AtomicBool, Ordering};
+
+        let published: Arc<PublishedVector<usize>> =
+            Arc::new(PublishedVector::new(std::iter::repeat_n(0, 1000).collect()));
+        let done = Arc::new(AtomicBool::new(false));
+        let mut readers = Vec::new();
+        for _ in 0..4 {
+            let published = published.clone();
+            let done = done.clone();
+            readers.push(std::thread::spawn(move || {
+                let mut loads = 0;
+                while !done.load(Ordering::SeqCst) || loads == 0 {
+                    let snapshot = published.load();
+                    assert_eq!(snapshot.len(), 1000);
+                    let version = snapshot[0];
+                    assert!(snapshot.iter().all(|v| *v == version));
+                    loads += 1;
+                }
+            }));
+        }
+        for version in 1..=20 {
+            published.store(std::iter::repeat_n(version, 1000).collect());
+            std::thread::yield_now();
+        }
+        done.store(true, Ordering::SeqCst);
+        for reader in readers {
+            reader.join().unwrap();
+        }
+        assert_eq!(published.load()[999], 20);
+        assert!(PublishedVector::<usize>::default().load().is_empty());
+    }
+
+    #[test]
+    fn test_cyclic_array_clone() {
+        let mut sut: CyclicArray<String> = CyclicArray::new(4);
+        sut.push_back("b".to_owned());
+        sut.push_back("c".to_owned());
+        sut.push_back("d".to_owned());
+        sut.push_front("a".to_owned());
+        let mut cloned = sut.clone();
+        assert_eq!(cloned.capacity(), 4);
+        assert!(cloned.is_full());
+        assert_eq!(cloned.as_slices(), sut.as_slices());
+        cloned.pop_front();
+        assert_eq!(cloned.get(0), Some(&"b".to_owned()));
+        assert_eq!(sut.get(0), Some(&"a".to_owned()));
+        assert_eq!(sut.get(3), Some(&"d".to_owned()));
+    }
+
+    #[test]
+    fn test_cyclic_array_debug() {
+        let mut sut: CyclicArray<usize> = CyclicArray::new(4);
+        sut.push_back(2);
+        sut.push_back(3);
+        sut.push_front(1);
+        sut.push_front(0);
+        assert_eq!(format!("{:?}", sut), "[0, 1, 2, 3]");
+        assert_eq!(
+            format!("{}", sut),
+            "CyclicArray(capacity: 4, head: 2, count: 4)"
+        );
+    }
+
     #[test]
     fn test_cyclic_array_zero_capacity() {
         let sut = CyclicArray::<usize>::new(0);
@@ -2200,6 +9278,18 @@ mod tests {
         assert_eq!(b[1], 9);
     }
 
+    #[test]
+    fn test_cyclic_array_as_slices() {
+        let mut sut = CyclicArray::<usize>::new(4);
+        assert!(sut.as_slices().0.is_empty());
+        sut.push_back(1);
+        sut.push_back(2);
+        assert_eq!(sut.as_slices(), (&[1, 2][..], &[][..]));
+        sut.push_front(0);
+        assert_eq!(sut.as_slices(), (&[0][..], &[1, 2][..]));
+        assert!(CyclicArray::<usize>::new(0).as_slices().1.is_empty());
+    }
+
     #[test]
     fn test_cyclic_array_get_mut() {
         let mut sut = CyclicArray::<usize>::new(4);
@@ -2219,3 +9309,153 @@ mod tests {
         assert_eq!(sut[3], 4);
     }
 }
+
+#[cfg(test)]
+mod drop_audit {
+    use super::*;
+    use std::sync::atomic::{AtomicIsize, Ordering};
+
+    /// number of live `Tracked` instances
+    static LIVE: AtomicIsize = AtomicIsize::new(0);
+
+    /// Element that counts the number of live instances.
+    struct Tracked(usize);
+
+    impl Tracked {
+        fn new(value: usize) -> Self {
+            LIVE.fetch_add(1, Ordering::SeqCst);
+            Self(value)
+        }
+    }
+
+    impl Clone for Tracked {
+        fn clone(&self) -> Self {
+            Tracked::new(self.0)
+        }
+    }
+
+    impl Drop for Tracked {
+        fn drop(&mut self) {
+            LIVE.fetch_sub(1, Ordering::SeqCst);
+        }
+    }
+
+    /// Asserts that the vector holds the same values as the model.
+    fn assert_matches(sut: &Vector<Tracked>, model: &[usize]) {
+        assert_eq!(sut.len(), model.len());
+        assert!(sut.iter().map(|t| t.0).eq(model.iter().copied()));
+    }
+
+    #[test]
+    fn test_drop_once_random_operations() {
+        {
+            let mut sut: Vector<Tracked> = Vector::new();
+            let mut model: Vec<usize> = Vec::new();
+            let mut next = 0;
+            for _ in 0..5_000 {
+                let len = model.len();
+                match rand::random_range(0..100) {
+                    0..30 => {
+                        sut.push(Tracked::new(next));
+                        model.push(next);
+                        next += 1;
+                    }
+                    30..45 => {
+                        let index = rand::random_range(0..=len);
+                        sut.insert(index, Tracked::new(next));
+                        model.insert(index, next);
+                        next += 1;
+                    }
+                    45..55 if len > 0 => {
+                        let index = rand::random_range(0..len);
+                        assert_eq!(sut.remove(index).0, model.remove(index));
+                    }
+                    55..62 => {
+                        assert_eq!(sut.pop().map(|t| t.0), model.pop());
+                    }
+                    62..67 => {
+                        // take only part of the drained range
+                        let start = rand::random_range(0..=len);
+                        let end = rand::random_range(start..=len);
+                        let taken: Vec<usize> =
+                            sut.drain(start..end).take(3).map(|t| t.0).collect();
+                        let expected: Vec<usize> = model.drain(start..end).take(3).collect();
+                        assert_eq!(taken, expected);
+                    }
+                    67..72 => {
+                        let parts = rand::random_range(1..5);
+                        match rand::random_range(0..3) {
+                            0 => {
+                                let pieces = std::mem::take(&mut sut).split_into(parts);
+                                sut = Vector::concat_vectors(pieces);
+                            }
+                            1 => {
+                                let pieces = std::mem::take(&mut sut).split_into(parts);
+                                sut = Vector::pack(pieces);
+                            }
+                            _ => {
+                                let mut tail = sut.split_off(rand::random_range(0..=len));
+                                sut.append(&mut tail);
+                            }
+                        }
+                    }
+                    72..76 => {
+                        // stop consuming partway through
+                        let keep = rand::random_range(0..=len);
+                        let mut iter = std::mem::take(&mut sut).into_iter();
+                        for _ in 0..keep {
+                            sut.push(iter.next().unwrap());
+                        }
+                        drop(iter);
+                        model.truncate(keep);
+                    }
+                    76..80 => {
+                        let len = rand::random_range(0..=len);
+                        sut.truncate(len);
+                        model.truncate(len);
+                    }
+                    80..84 => {
+                        let modulus = rand::random_range(2..5);
+                        sut.retain(|t| t.0 % modulus != 0);
+                        model.retain(|v| v % modulus != 0);
+                    }
+                    84..88 if len > 1 => {
+                        let a = rand::random_range(0..len);
+                        let b = rand::random_range(0..len);
+                        sut.swap(a, b);
+                        model.swap(a, b);
+                    }
+                    88..92 => {
+                        let copy = sut.clone();
+                        sut = copy.reversed();
+                        model.reverse();
+                    }
+                    92..96 => {
+                        let removals = rand::random_range(0..=len.min(10));
+                        sut.batch(|batch| {
+                            for _ in 0..removals {
+                                batch.remove(0);
+                            }
+                            batch.push(Tracked::new(next));
+                        });
+                        model.drain(..removals);
+                        model.push(next);
+                        next += 1;
+                    }
+                    96..98 => {
+                        sut.clear_keep_capacity();
+                        model.clear();
+                    }
+                    98..100 => {
+                        sut.clear();
+                        model.clear();
+                    }
+                    _ => (),
+                }
+                assert_matches(&sut, &model);
+                assert_eq!(LIVE.load(Ordering::SeqCst), model.len() as isize);
+            }
+        }
+        assert_eq!(LIVE.load(Ordering::SeqCst), 0);
+    }
+}